@@ -0,0 +1,223 @@
+// Typed accessors for attributes and JSON Pointers (RFC 6901) of any JsonType.
+// They replace chains like `value.get_attribute("x").and_then(JsonType::as_string).ok_or(...)` and report
+// `Error::TypedAccessFailed` with the accessed path and the reason of the failure (ie. the expected and actual types).
+use crate::{
+    error::Error,
+    fragment_helpers::fragment_from_fragment_components,
+    json_type::{resolve_fragment, JsonMap, JsonType},
+};
+use std::{convert::TryFrom, iter::once};
+
+fn access<'json, T: JsonType, V>(pointer: &str, value: Result<&'json T, Error>, extract: impl FnOnce(&'json T) -> Result<V, Error>) -> Result<V, Error> {
+    value.and_then(extract).map_err(|reason| Error::TypedAccessFailed {
+        pointer: pointer.to_string(),
+        reason: Box::new(reason),
+    })
+}
+
+fn attribute<'json, T: JsonType, V>(value: &'json T, key: &str, extract: impl FnOnce(&'json T) -> Result<V, Error>) -> Result<V, Error> {
+    access(&fragment_from_fragment_components(once(key)), value.try_get_attribute(key), extract)
+}
+
+fn pointer<'json, T: JsonType, V>(value: &'json T, pointer: &str, extract: impl FnOnce(&'json T) -> Result<V, Error>) -> Result<V, Error> {
+    access(pointer, resolve_fragment(value, pointer), extract)
+}
+
+fn as_i64<T: JsonType>(value: &T) -> Result<i64, Error> {
+    let integer = value.try_as_integer()?;
+    i64::try_from(integer).map_err(|_| Error::IntegerOutOfRange { value: integer })
+}
+
+fn as_u64<T: JsonType>(value: &T) -> Result<u64, Error> {
+    let integer = value.try_as_integer()?;
+    u64::try_from(integer).map_err(|_| Error::IntegerOutOfRange { value: integer })
+}
+
+// Integers are accepted as well, as they are numbers for JSON
+#[allow(clippy::cast_precision_loss)]
+fn as_f64<T: JsonType>(value: &T) -> Result<f64, Error> {
+    value.as_integer().map_or_else(|| value.try_as_number(), |integer| Ok(integer as f64))
+}
+
+/// Typed accessors of object attributes (`get_*`) and of values referenced by JSON Pointers (`pointer_*`)
+///
+/// # Errors
+/// All the accessors report `Error::TypedAccessFailed` if the value is missing, if it is of an unexpected primitive
+/// type or if the integer does not fit the requested type
+#[allow(clippy::missing_errors_doc)]
+pub trait TypedAccessors: JsonType + Sized {
+    fn get_str(&self, key: &str) -> Result<&str, Error> {
+        attribute(self, key, JsonType::try_as_string)
+    }
+
+    fn get_bool(&self, key: &str) -> Result<bool, Error> {
+        attribute(self, key, JsonType::try_as_boolean)
+    }
+
+    fn get_i64(&self, key: &str) -> Result<i64, Error> {
+        attribute(self, key, as_i64)
+    }
+
+    fn get_u64(&self, key: &str) -> Result<u64, Error> {
+        attribute(self, key, as_u64)
+    }
+
+    fn get_f64(&self, key: &str) -> Result<f64, Error> {
+        attribute(self, key, as_f64)
+    }
+
+    fn get_array<'json>(&'json self, key: &str) -> Result<Box<dyn ExactSizeIterator<Item = &'json Self> + 'json>, Error> {
+        attribute(self, key, JsonType::try_as_array)
+    }
+
+    fn get_object(&self, key: &str) -> Result<JsonMap<'_, Self>, Error> {
+        attribute(self, key, JsonType::try_as_object)
+    }
+
+    fn pointer_str(&self, fragment: &str) -> Result<&str, Error> {
+        pointer(self, fragment, JsonType::try_as_string)
+    }
+
+    fn pointer_bool(&self, fragment: &str) -> Result<bool, Error> {
+        pointer(self, fragment, JsonType::try_as_boolean)
+    }
+
+    fn pointer_i64(&self, fragment: &str) -> Result<i64, Error> {
+        pointer(self, fragment, as_i64)
+    }
+
+    fn pointer_u64(&self, fragment: &str) -> Result<u64, Error> {
+        pointer(self, fragment, as_u64)
+    }
+
+    fn pointer_f64(&self, fragment: &str) -> Result<f64, Error> {
+        pointer(self, fragment, as_f64)
+    }
+
+    fn pointer_array<'json>(&'json self, fragment: &str) -> Result<Box<dyn ExactSizeIterator<Item = &'json Self> + 'json>, Error> {
+        pointer(self, fragment, JsonType::try_as_array)
+    }
+
+    fn pointer_object(&self, fragment: &str) -> Result<JsonMap<'_, Self>, Error> {
+        pointer(self, fragment, JsonType::try_as_object)
+    }
+}
+
+impl<T: JsonType> TypedAccessors for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::TypedAccessors;
+    use crate::{
+        error::{Error, PointerError, PointerErrorReason},
+        json_type::{JsonMapTrait, PrimitiveType},
+        rust_type_impl::RustType,
+    };
+
+    lazy_static! {
+        static ref DOCUMENT: RustType = rust_type!({
+            "name": "John",
+            "active": true,
+            "age": 42,
+            "balance": -1.5,
+            "big": RustType::from(i128::from(u64::MAX)),
+            "tags": ["a", "b"],
+            "address": {"city": "Rome", "zip": 100},
+        });
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(DOCUMENT.get_str("name"), Ok("John"));
+        assert_eq!(DOCUMENT.get_bool("active"), Ok(true));
+        assert_eq!(DOCUMENT.get_i64("age"), Ok(42));
+        assert_eq!(DOCUMENT.get_u64("big"), Ok(u64::MAX));
+        assert_eq!(DOCUMENT.get_f64("balance"), Ok(-1.5));
+        assert_eq!(DOCUMENT.get_f64("age"), Ok(42.0));
+        assert_eq!(DOCUMENT.get_array("tags").map(|items| items.len()), Ok(2));
+        assert_eq!(
+            DOCUMENT.get_object("address").map(|object| object.keys().collect::<Vec<_>>().join(",")),
+            Ok("city,zip".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pointer() {
+        assert_eq!(DOCUMENT.pointer_str("/address/city"), Ok("Rome"));
+        assert_eq!(DOCUMENT.pointer_u64("/address/zip"), Ok(100));
+        assert_eq!(DOCUMENT.pointer_str("/tags/1"), Ok("b"));
+        assert_eq!(DOCUMENT.pointer_object("").map(|object| object.keys().count()), Ok(7));
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let error = DOCUMENT.get_str("age").unwrap_err();
+        assert_eq!(
+            error,
+            Error::TypedAccessFailed {
+                pointer: "/age".to_string(),
+                reason: Box::new(Error::UnexpectedPrimitiveType {
+                    expected: PrimitiveType::String,
+                    actual: PrimitiveType::Integer,
+                }),
+            }
+        );
+        assert_eq!(error.to_string(), "Unable to access `/age`: Expected a value of primitive type `String`, found `Integer`");
+    }
+
+    #[test]
+    fn test_integer_out_of_range() {
+        assert_eq!(
+            DOCUMENT.get_i64("big"),
+            Err(Error::TypedAccessFailed {
+                pointer: "/big".to_string(),
+                reason: Box::new(Error::IntegerOutOfRange { value: i128::from(u64::MAX) }),
+            })
+        );
+        assert_eq!(
+            DOCUMENT.pointer_u64("/balance"),
+            Err(Error::TypedAccessFailed {
+                pointer: "/balance".to_string(),
+                reason: Box::new(Error::UnexpectedPrimitiveType {
+                    expected: PrimitiveType::Integer,
+                    actual: PrimitiveType::Number,
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_value() {
+        assert_eq!(
+            DOCUMENT.pointer_str("/address/street"),
+            Err(Error::TypedAccessFailed {
+                pointer: "/address/street".to_string(),
+                reason: Box::new(Error::InvalidPointer(PointerError {
+                    fragment: "/address/street".to_string(),
+                    component_index: 1,
+                    component: "street".to_string(),
+                    resolved_fragment: "/address".to_string(),
+                    primitive_type: PrimitiveType::Object,
+                    reason: PointerErrorReason::MissingAttribute,
+                })),
+            })
+        );
+        assert!(matches!(rust_type!([1]).get_i64("0"), Err(Error::TypedAccessFailed { .. })));
+    }
+}
+
+#[cfg(all(test, feature = "trait_serde_json"))]
+mod tests_serde_json {
+    use super::TypedAccessors;
+
+    #[test]
+    fn test_serde_json_accessors() {
+        let value = json!({"status": {"code": 404, "reason": "Not Found"}});
+        assert_eq!(value.pointer_i64("/status/code"), Ok(404));
+        assert_eq!(value.get_object("status").map(|_| ()), Ok(()));
+        assert_eq!(
+            value.pointer_bool("/status/reason").unwrap_err().to_string(),
+            "Unable to access `/status/reason`: Expected a value of primitive type `Boolean`, found `String`"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+// Semantic comparison of JsonType instances, possibly backed by different types.
+// The comparison walks both objects lazily, so no intermediate RustType copy is allocated.
+use crate::json_type::{JsonMapTrait, JsonType, PrimitiveType};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NumericComparison {
+    // Integers and numbers are different values (ie. `1` is different from `1.0`)
+    Strict,
+    // Integers and numbers are equal if they represent the same mathematical value (ie. `1` is equal to `1.0`)
+    #[default]
+    IntegerFloatEquivalent,
+    // Numeric values are equal if their difference is not greater than the given epsilon.
+    // NOTE: This relation is not transitive, so `json_cmp` does not define a total ordering with this comparison.
+    Epsilon(f64),
+}
+
+// Rank of the primitive types used to sort values of different types.
+// Integers and numbers have the same rank as they are compared by value.
+const fn primitive_type_rank(primitive_type: PrimitiveType) -> u8 {
+    match primitive_type {
+        PrimitiveType::Null => 0,
+        PrimitiveType::Boolean => 1,
+        PrimitiveType::Integer | PrimitiveType::Number => 2,
+        PrimitiveType::String => 3,
+        PrimitiveType::Array => 4,
+        PrimitiveType::Object => 5,
+    }
+}
+
+// NaN values are not valid JSON, but they might be stored by some backends.
+// To guarantee a total ordering NaN is considered equal to itself and greater than any other number.
+fn compare_numbers(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn compare_integer_with_number(integer: i128, number: f64) -> Ordering {
+    // 2^127 is exactly representable as f64 and it is the first value out of the i128 range
+    let i128_upper_bound = 2_f64.powi(127);
+    if number.is_nan() || number >= i128_upper_bound {
+        Ordering::Less
+    } else if number < -i128_upper_bound {
+        Ordering::Greater
+    } else {
+        let truncated_number = number.trunc();
+        integer.cmp(&(truncated_number as i128)).then_with(|| compare_numbers(truncated_number, number))
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn compare_numeric<A: JsonType, B: JsonType>(a: &A, b: &B, numeric_comparison: NumericComparison) -> Ordering {
+    let exact_ordering = match (a.as_integer(), b.as_integer()) {
+        (Some(a_integer), Some(b_integer)) => a_integer.cmp(&b_integer),
+        (Some(a_integer), None) => compare_integer_with_number(a_integer, b.as_number().unwrap_or(f64::NAN)),
+        (None, Some(b_integer)) => compare_integer_with_number(b_integer, a.as_number().unwrap_or(f64::NAN)).reverse(),
+        (None, None) => compare_numbers(a.as_number().unwrap_or(f64::NAN), b.as_number().unwrap_or(f64::NAN)),
+    };
+    match numeric_comparison {
+        // Integers are sorted before numbers representing the same value
+        NumericComparison::Strict => exact_ordering.then_with(|| a.is_integer().cmp(&b.is_integer()).reverse()),
+        NumericComparison::IntegerFloatEquivalent => exact_ordering,
+        NumericComparison::Epsilon(epsilon) => {
+            let a_number = a.as_integer().map_or_else(|| a.as_number().unwrap_or(f64::NAN), |integer| integer as f64);
+            let b_number = b.as_integer().map_or_else(|| b.as_number().unwrap_or(f64::NAN), |integer| integer as f64);
+            if (a_number - b_number).abs() <= epsilon {
+                Ordering::Equal
+            } else {
+                exact_ordering
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn json_eq<A: JsonType, B: JsonType>(a: &A, b: &B) -> bool {
+    json_eq_with(a, b, NumericComparison::default())
+}
+
+#[must_use]
+pub fn json_eq_with<A: JsonType, B: JsonType>(a: &A, b: &B, numeric_comparison: NumericComparison) -> bool {
+    // Values not recognized as any JSON primitive type are not equal to anything (as NaN)
+    match (a.try_primitive_type(), b.try_primitive_type()) {
+        (Ok(PrimitiveType::Array), Ok(PrimitiveType::Array)) => {
+            if let (Some(a_items), Some(b_items)) = (a.as_array(), b.as_array()) {
+                a_items.len() == b_items.len() && a_items.zip(b_items).all(|(a_item, b_item)| json_eq_with(a_item, b_item, numeric_comparison))
+            } else {
+                false
+            }
+        }
+        (Ok(PrimitiveType::Boolean), Ok(PrimitiveType::Boolean)) => a.as_boolean() == b.as_boolean(),
+        (Ok(PrimitiveType::Null), Ok(PrimitiveType::Null)) => true,
+        (Ok(PrimitiveType::Object), Ok(PrimitiveType::Object)) => {
+            if let (Some(a_object), Some(b_object)) = (a.as_object(), b.as_object()) {
+                a_object.keys().count() == b_object.keys().count()
+                    && a_object
+                        .items()
+                        .all(|(key, a_value)| b.get_attribute(key).is_some_and(|b_value| json_eq_with(a_value, b_value, numeric_comparison)))
+            } else {
+                false
+            }
+        }
+        (Ok(PrimitiveType::String), Ok(PrimitiveType::String)) => a.as_string() == b.as_string(),
+        (Ok(PrimitiveType::Integer | PrimitiveType::Number), Ok(PrimitiveType::Integer | PrimitiveType::Number)) => compare_numeric(a, b, numeric_comparison) == Ordering::Equal,
+        _ => false,
+    }
+}
+
+/// Total ordering of `JsonType` instances.
+///
+/// Values of different primitive types are sorted as null < boolean < numbers < string < array < object,
+/// arrays are compared lexicographically and objects are compared as lists of (key, value) pairs sorted by key.
+///
+/// # Panics
+/// The function panics if a compared value is not recognized as a JSON primitive type, such values have no position
+/// in the total ordering (unlike `json_eq`, which reports them as different from anything).
+#[must_use]
+pub fn json_cmp<A: JsonType, B: JsonType>(a: &A, b: &B) -> Ordering {
+    json_cmp_with(a, b, NumericComparison::default())
+}
+
+/// # Panics
+/// See `json_cmp`
+#[must_use]
+pub fn json_cmp_with<A: JsonType, B: JsonType>(a: &A, b: &B, numeric_comparison: NumericComparison) -> Ordering {
+    let (a_primitive_type, b_primitive_type) = (a.primitive_type(), b.primitive_type());
+    let rank_ordering = primitive_type_rank(a_primitive_type).cmp(&primitive_type_rank(b_primitive_type));
+    if rank_ordering != Ordering::Equal {
+        return rank_ordering;
+    }
+
+    match a_primitive_type {
+        PrimitiveType::Array => {
+            if let (Some(a_items), Some(b_items)) = (a.as_array(), b.as_array()) {
+                let (a_length, b_length) = (a_items.len(), b_items.len());
+                a_items
+                    .zip(b_items)
+                    .map(|(a_item, b_item)| json_cmp_with(a_item, b_item, numeric_comparison))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or_else(|| a_length.cmp(&b_length))
+            } else {
+                Ordering::Equal
+            }
+        }
+        PrimitiveType::Boolean => a.as_boolean().cmp(&b.as_boolean()),
+        PrimitiveType::Null => Ordering::Equal,
+        PrimitiveType::Object => {
+            if let (Some(a_object), Some(b_object)) = (a.as_object(), b.as_object()) {
+                let mut a_items = a_object.items().collect::<Vec<_>>();
+                let mut b_items = b_object.items().collect::<Vec<_>>();
+                a_items.sort_by_key(|(key, _)| *key);
+                b_items.sort_by_key(|(key, _)| *key);
+                a_items
+                    .iter()
+                    .zip(b_items.iter())
+                    .map(|((a_key, a_value), (b_key, b_value))| a_key.cmp(b_key).then_with(|| json_cmp_with(*a_value, *b_value, numeric_comparison)))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or_else(|| a_items.len().cmp(&b_items.len()))
+            } else {
+                Ordering::Equal
+            }
+        }
+        PrimitiveType::String => a.as_string().cmp(&b.as_string()),
+        PrimitiveType::Integer | PrimitiveType::Number => compare_numeric(a, b, numeric_comparison),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_cmp, json_cmp_with, json_eq, json_eq_with, NumericComparison};
+    use crate::{rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use std::cmp::Ordering;
+    use test_case::test_case;
+
+    #[test_case(&rust_type!(null), &rust_type!(null) => true)]
+    #[test_case(&rust_type!(1), &rust_type!(1.0) => true)]
+    #[test_case(&rust_type!(1), &rust_type!(1.5) => false)]
+    #[test_case(&rust_type!(1), &rust_type!("1") => false)]
+    #[test_case(&rust_type!({"a": [1, {"b": null}], "c": "d"}), &rust_type!({"c": "d", "a": [1.0, {"b": null}]}) => true ; "nested objects")]
+    #[test_case(&rust_type!({"a": 1}), &rust_type!({"a": 1, "b": 2}) => false ; "different object keys")]
+    #[test_case(&rust_type!([1, 2]), &rust_type!([2, 1]) => false ; "different array order")]
+    fn test_json_eq(a: &RustType, b: &RustType) -> bool {
+        json_eq(a, b)
+    }
+
+    #[test_case(&rust_type!(1), &rust_type!(1.0), NumericComparison::Strict => false)]
+    #[test_case(&rust_type!(1), &rust_type!(1), NumericComparison::Strict => true)]
+    #[test_case(&rust_type!(1.0), &rust_type!(1.0), NumericComparison::Strict => true)]
+    #[test_case(&rust_type!(1), &rust_type!(1.0), NumericComparison::IntegerFloatEquivalent => true)]
+    #[test_case(&RustType::Integer(i128::MAX), &rust_type!(1.701_411_834_604_692_3e38), NumericComparison::IntegerFloatEquivalent => false)]
+    #[test_case(&rust_type!(0.1), &rust_type!(0.100_000_1), NumericComparison::Epsilon(1e-6) => true)]
+    #[test_case(&rust_type!(1), &rust_type!(1.000_000_1), NumericComparison::Epsilon(1e-6) => true)]
+    #[test_case(&rust_type!(1), &rust_type!(1.1), NumericComparison::Epsilon(1e-6) => false)]
+    fn test_json_eq_with(a: &RustType, b: &RustType, numeric_comparison: NumericComparison) -> bool {
+        json_eq_with(a, b, numeric_comparison)
+    }
+
+    #[test]
+    fn test_json_eq_with_unrecognized_values() {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        assert!(!json_eq(&value, &value));
+        assert!(!json_eq(&value, &rust_type!([1, null])));
+        assert!(json_eq(&PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1)]), &rust_type!([1.0])));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match any JSON primitive type")]
+    fn test_json_cmp_with_unrecognized_values() {
+        let _ = json_cmp(&PartiallyRecognizedValue::Unrecognized, &rust_type!(null));
+    }
+
+    #[test_case(&rust_type!(null), &rust_type!(false) => Ordering::Less)]
+    #[test_case(&rust_type!(true), &rust_type!(0) => Ordering::Less)]
+    #[test_case(&rust_type!(1), &rust_type!(0.5) => Ordering::Greater)]
+    #[test_case(&rust_type!(-1), &rust_type!(-0.5) => Ordering::Less)]
+    #[test_case(&rust_type!(2), &rust_type!(2.0) => Ordering::Equal)]
+    #[test_case(&rust_type!(2.0), &rust_type!(f64::NAN) => Ordering::Less)]
+    #[test_case(&rust_type!(f64::NAN), &rust_type!(f64::NAN) => Ordering::Equal)]
+    #[test_case(&rust_type!(3), &rust_type!("2") => Ordering::Less)]
+    #[test_case(&rust_type!("a"), &rust_type!("b") => Ordering::Less)]
+    #[test_case(&rust_type!("z"), &rust_type!([]) => Ordering::Less)]
+    #[test_case(&rust_type!([1, 2]), &rust_type!([1, 3]) => Ordering::Less)]
+    #[test_case(&rust_type!([1, 2]), &rust_type!([1]) => Ordering::Greater)]
+    #[test_case(&rust_type!([]), &rust_type!({}) => Ordering::Less)]
+    #[test_case(&rust_type!({"a": 1, "b": 2}), &rust_type!({"b": 2, "a": 1.0}) => Ordering::Equal)]
+    #[test_case(&rust_type!({"a": 1}), &rust_type!({"b": 0}) => Ordering::Less)]
+    #[test_case(&rust_type!({"a": 2}), &rust_type!({"a": 1}) => Ordering::Greater)]
+    #[test_case(&rust_type!({"a": 1}), &rust_type!({"a": 1, "b": 1}) => Ordering::Less)]
+    fn test_json_cmp(a: &RustType, b: &RustType) -> Ordering {
+        json_cmp(a, b)
+    }
+
+    #[test_case(&rust_type!(2), &rust_type!(2.0) => Ordering::Less)]
+    #[test_case(&rust_type!(2.0), &rust_type!(2) => Ordering::Greater)]
+    #[test_case(&rust_type!(3), &rust_type!(2.0) => Ordering::Greater)]
+    fn test_json_cmp_strict(a: &RustType, b: &RustType) -> Ordering {
+        json_cmp_with(a, b, NumericComparison::Strict)
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_json_eq_between_different_types() {
+        assert!(json_eq(&rust_type!({"a": [1, 2.5, null], "b": "c"}), &json!({"b": "c", "a": [1.0, 2.5, null]})));
+        assert!(!json_eq(&rust_type!({"a": [1]}), &json!({"a": [1, 2]})));
+    }
+}
@@ -0,0 +1,338 @@
+// Conversion between JsonType backends without RustType as intermediate representation
+use crate::{
+    error::Error,
+    json_type::{JsonMapTrait, JsonType, JsonTypeBuilder, PrimitiveType},
+    serializer::NonFiniteNumberPolicy,
+};
+
+// Build a backend value from an integer, None is returned if the integer is outside of the i64/u64 range
+#[cfg(any(feature = "trait_json", feature = "trait_serde_json", feature = "trait_serde_yaml"))]
+pub(crate) fn try_from_i128_without_fallback<T: From<i64> + From<u64>>(value: i128) -> Option<T> {
+    use std::convert::TryFrom;
+    i64::try_from(value).map(T::from).or_else(|_| u64::try_from(value).map(T::from)).ok()
+}
+
+// Build a backend value from an integer, backends not supporting integers outside of the i64/u64 range
+// fallback to a (lossy) float representation
+#[cfg(any(feature = "trait_json", feature = "trait_serde_json", feature = "trait_serde_yaml"))]
+pub(crate) fn from_i128_with_float_fallback<T: From<i64> + From<u64> + From<f64>>(value: i128) -> T {
+    try_from_i128_without_fallback(value).unwrap_or_else(|| {
+        #[allow(clippy::cast_precision_loss)]
+        T::from(value as f64)
+    })
+}
+
+// Integers that cannot be exactly represented by the target backend (ie. integers outside of the i64/u64 range for serde_json)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntegerOverflowPolicy {
+    Error,
+    // Use the closest float representation
+    Float,
+}
+
+// Mapping keys that are not strings (ie. `1: value` in YAML)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NonStringKeyPolicy {
+    Error,
+    Skip,
+    // Use the string representation of scalar keys (`1` -> `"1"`), non scalar keys are still reported as errors
+    Stringify,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConversionOptions {
+    pub integer_overflow: IntegerOverflowPolicy,
+    pub non_finite_numbers: NonFiniteNumberPolicy,
+    pub non_string_keys: NonStringKeyPolicy,
+}
+
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl ConversionOptions {
+    // Report every lossy conversion as error
+    #[must_use]
+    pub const fn strict() -> Self {
+        Self {
+            integer_overflow: IntegerOverflowPolicy::Error,
+            non_finite_numbers: NonFiniteNumberPolicy::Error,
+            non_string_keys: NonStringKeyPolicy::Error,
+        }
+    }
+
+    // Approximate whatever cannot be exactly represented
+    #[must_use]
+    pub const fn lossy() -> Self {
+        Self {
+            integer_overflow: IntegerOverflowPolicy::Float,
+            non_finite_numbers: NonFiniteNumberPolicy::Null,
+            non_string_keys: NonStringKeyPolicy::Stringify,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_integer_overflow(mut self, integer_overflow: IntegerOverflowPolicy) -> Self {
+        self.integer_overflow = integer_overflow;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_non_finite_numbers(mut self, non_finite_numbers: NonFiniteNumberPolicy) -> Self {
+        self.non_finite_numbers = non_finite_numbers;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_non_string_keys(mut self, non_string_keys: NonStringKeyPolicy) -> Self {
+        self.non_string_keys = non_string_keys;
+        self
+    }
+}
+
+// Attributes of the objects to convert.
+// JsonMapTrait exposes only string keys, so backends allowing other keys specialise the trait to apply `NonStringKeyPolicy`
+trait ConversionSource: JsonType + Sized {
+    fn attributes(&self, non_string_keys: NonStringKeyPolicy) -> Result<Vec<(String, &Self)>, Error>;
+}
+
+impl<T: JsonType> ConversionSource for T {
+    default fn attributes(&self, _: NonStringKeyPolicy) -> Result<Vec<(String, &Self)>, Error> {
+        let keys = self
+            .as_object()
+            .map(|object| object.keys().map(ToString::to_string).collect::<Vec<_>>())
+            .unwrap_or_default();
+        Ok(keys.into_iter().filter_map(|key| self.get_attribute(&key).map(|value| (key, value))).collect())
+    }
+}
+
+#[cfg(feature = "trait_serde_yaml")]
+impl ConversionSource for serde_yaml::Value {
+    fn attributes(&self, non_string_keys: NonStringKeyPolicy) -> Result<Vec<(String, &Self)>, Error> {
+        let mut attributes = Vec::new();
+        for (key, value) in self.as_mapping().into_iter().flatten() {
+            if let Some(key) = crate::traits::_serde_yaml::string_key(key, non_string_keys)? {
+                attributes.push((key, value));
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+fn convert_node<Source: ConversionSource, Target: JsonTypeBuilder>(value: &Source, options: ConversionOptions) -> Result<Target, Error> {
+    match value.try_primitive_type()? {
+        PrimitiveType::Array => Ok(Target::from_array(
+            value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|item| convert_node(item, options))
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        PrimitiveType::Boolean => Ok(Target::from_bool(value.as_boolean().unwrap_or_default())),
+        PrimitiveType::Integer => {
+            let integer = value.as_integer().unwrap_or_default();
+            Target::try_from_i128(integer).map_or_else(
+                || match options.integer_overflow {
+                    IntegerOverflowPolicy::Error => Err(Error::IntegerOutOfRange { value: integer }),
+                    IntegerOverflowPolicy::Float => Ok(Target::from_i128(integer)),
+                },
+                Ok,
+            )
+        }
+        PrimitiveType::Null => Ok(Target::null()),
+        PrimitiveType::Number => {
+            let number = value.as_number().unwrap_or_default();
+            Target::try_from_f64(number).map_or_else(
+                || match options.non_finite_numbers {
+                    NonFiniteNumberPolicy::Error => Err(Error::NonFiniteNumber { value: format!("{number}") }),
+                    NonFiniteNumberPolicy::Null => Ok(Target::null()),
+                },
+                Ok,
+            )
+        }
+        PrimitiveType::Object => Ok(Target::from_object(
+            value
+                .attributes(options.non_string_keys)?
+                .into_iter()
+                .map(|(key, attribute_value)| convert_node(attribute_value, options).map(|converted| (key, converted)))
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        PrimitiveType::String => Ok(Target::from_string(value.as_string().unwrap_or_default())),
+    }
+}
+
+/// Convert `source` into the `Target` backend reporting, as errors, all the values that cannot be exactly represented.
+///
+/// # Errors
+/// `Error::IntegerOutOfRange`, `Error::NonFiniteNumber` or `Error::NonStringKey` if `source` contains values that the
+/// target backend cannot represent (see `ConversionOptions::strict`).
+pub fn convert_into<Target: JsonTypeBuilder>(source: &impl JsonType) -> Result<Target, Error> {
+    convert_into_with_options(source, ConversionOptions::strict())
+}
+
+/// Convert `source` into the `Target` backend applying the policies defined by `options` to lossy conversions.
+///
+/// # Errors
+/// `Error::IntegerOutOfRange`, `Error::NonFiniteNumber` or `Error::NonStringKey` if `source` contains values that the
+/// target backend cannot represent and the corresponding policy is `Error`.
+pub fn convert_into_with_options<Target: JsonTypeBuilder>(source: &impl JsonType, options: ConversionOptions) -> Result<Target, Error> {
+    convert_node(source, options)
+}
+
+/// Build a `Target` instance equivalent to `value`.
+/// Values that cannot be exactly represented by `Target` are approximated (see `ConversionOptions::lossy`).
+///
+/// # Errors
+/// `Error::UnrecognizedValue` if `value`, or any of its descendants, is not recognized as a JSON primitive type.
+/// `Error::NonStringKey` if `value` contains non scalar mapping keys (ie. `serde_yaml` sequences used as keys).
+pub fn convert<Source: JsonType, Target: JsonTypeBuilder>(value: &Source) -> Result<Target, Error> {
+    convert_into_with_options(value, ConversionOptions::lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert, convert_into};
+    use crate::{
+        error::Error,
+        json_type::{JsonType, JsonTypeBuilder},
+        rust_type_impl::RustType,
+        test_helpers::PartiallyRecognizedValue,
+    };
+
+    // Generic function producing any backend, possible only thanks to JsonTypeBuilder
+    pub(super) fn build_document<T: JsonTypeBuilder>() -> T {
+        T::from_object(vec![
+            ("null".to_string(), T::null()),
+            ("boolean".to_string(), T::from_bool(true)),
+            ("integer".to_string(), T::from_i128(1)),
+            ("number".to_string(), T::from_f64(2.5)),
+            ("items".to_string(), T::from_array(vec![T::from_string("a"), T::from_string("b")])),
+        ])
+    }
+
+    #[test]
+    fn test_rust_type_builder() {
+        assert_eq!(
+            build_document::<RustType>(),
+            rust_type!({"null": null, "boolean": true, "integer": 1, "number": 2.5, "items": ["a", "b"]})
+        );
+    }
+
+    #[test]
+    fn test_from_object_duplicated_keys() {
+        assert_eq!(
+            RustType::from_object(vec![("a".to_string(), RustType::from_i128(1)), ("a".to_string(), RustType::from_i128(2))]),
+            rust_type!({"a": 2})
+        );
+    }
+
+    #[test]
+    fn test_convert_unrecognized_value() {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        assert_eq!(convert_into::<RustType>(&value), Err(Error::UnrecognizedValue));
+        assert_eq!(convert::<_, RustType>(&value), Err(Error::UnrecognizedValue));
+        assert_eq!(convert::<_, RustType>(&PartiallyRecognizedValue::Integer(1)), Ok(rust_type!(1)));
+    }
+
+    #[test]
+    fn test_convert_into_rust_type_is_lossless() {
+        let value = RustType::from(vec![RustType::from(i128::MAX), RustType::from(f64::NAN)]);
+        let converted = convert_into::<RustType>(&value).unwrap();
+        assert_eq!(converted.get_index(0), Some(&RustType::from(i128::MAX)));
+        assert!(converted.get_index(1).and_then(JsonType::as_number).unwrap().is_nan());
+    }
+}
+
+#[cfg(all(test, feature = "trait_serde_json"))]
+mod tests_serde_json {
+    use super::{convert, convert_into_with_options, ConversionOptions, IntegerOverflowPolicy};
+    use crate::{error::Error, json_type::JsonTypeBuilder, serializer::NonFiniteNumberPolicy};
+    use test_case::test_case;
+
+    #[test]
+    fn test_serde_json_builder() {
+        assert_eq!(
+            super::tests::build_document::<serde_json::Value>(),
+            json!({"null": null, "boolean": true, "integer": 1, "number": 2.5, "items": ["a", "b"]})
+        );
+        // serde_json does not support integers outside of the i64/u64 range nor non finite numbers
+        assert_eq!(serde_json::Value::from_i128(i128::from(u64::MAX) + 1), json!(18_446_744_073_709_551_616.0));
+        assert_eq!(serde_json::Value::from_f64(f64::NAN), json!(null));
+    }
+
+    #[test_case(ConversionOptions::strict() => Err(Error::IntegerOutOfRange { value: i128::from(u64::MAX) + 1 }))]
+    #[test_case(ConversionOptions::strict().with_integer_overflow(IntegerOverflowPolicy::Float) => Ok(json!([18_446_744_073_709_551_615_u64, 18_446_744_073_709_551_616.0])))]
+    fn test_convert_into_integer_overflow(options: ConversionOptions) -> Result<serde_json::Value, Error> {
+        convert_into_with_options(&rust_type!([18_446_744_073_709_551_615_i128, 18_446_744_073_709_551_616_i128]), options)
+    }
+
+    #[test_case(ConversionOptions::strict() => Err(Error::NonFiniteNumber { value: "inf".to_string() }))]
+    #[test_case(ConversionOptions::strict().with_non_finite_numbers(NonFiniteNumberPolicy::Null) => Ok(json!({"a": null, "b": 1.5})))]
+    fn test_convert_into_non_finite_numbers(options: ConversionOptions) -> Result<serde_json::Value, Error> {
+        convert_into_with_options(&rust_type!({"a": f64::INFINITY, "b": 1.5}), options)
+    }
+
+    #[test]
+    fn test_convert_approximates_values_not_representable_by_the_target() {
+        assert_eq!(
+            convert::<_, serde_json::Value>(&rust_type!([18_446_744_073_709_551_616_i128, f64::NAN])),
+            Ok(json!([18_446_744_073_709_551_616.0, null]))
+        );
+    }
+}
+
+#[cfg(all(test, feature = "trait_serde_json", feature = "trait_serde_yaml"))]
+mod tests_serde_yaml {
+    use super::{convert, convert_into, convert_into_with_options, ConversionOptions, NonStringKeyPolicy};
+    use crate::{
+        error::Error,
+        json_type::{JsonMapTrait, JsonType},
+        rust_type_impl::RustType,
+    };
+    use test_case::test_case;
+
+    #[test]
+    fn test_convert_serde_yaml_to_serde_json() {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str("z: [1, 2.5, ~]\na: {b: true, c: text}").unwrap();
+        let json_value = convert::<_, serde_json::Value>(&yaml_value).unwrap();
+        assert_eq!(json_value, json!({"z": [1, 2.5, null], "a": {"b": true, "c": "text"}}));
+        // Roundtrip preserves the whole document (key order included)
+        assert_eq!(convert::<_, serde_yaml::Value>(&json_value).unwrap(), yaml_value);
+    }
+
+    #[test_case("{1: a, true: b, ~: c, x: d}", NonStringKeyPolicy::Error => Err(Error::NonStringKey { key: "1".to_string() }))]
+    #[test_case("{1: a, true: b, ~: c, x: d}", NonStringKeyPolicy::Skip => Ok(json!({"x": "d"})))]
+    #[test_case("{1: a, true: b, ~: c, x: d}", NonStringKeyPolicy::Stringify => Ok(json!({"1": "a", "true": "b", "null": "c", "x": "d"})))]
+    #[test_case("{[1, 2]: a}", NonStringKeyPolicy::Stringify => Err(Error::NonStringKey { key: "Sequence [Number(1), Number(2)]".to_string() }) ; "non scalar key")]
+    #[test_case("{200: {description: OK}}", NonStringKeyPolicy::Stringify => Ok(json!({"200": {"description": "OK"}})) ; "nested")]
+    fn test_convert_into_non_string_keys(yaml_str: &str, non_string_keys: NonStringKeyPolicy) -> Result<serde_json::Value, Error> {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str(yaml_str).unwrap();
+        convert_into_with_options(&yaml_value, ConversionOptions::strict().with_non_string_keys(non_string_keys))
+    }
+
+    #[test]
+    fn test_convert_into_preserves_key_order_and_integers() {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str("z: 1\na: 18446744073709551615\nm: 0.5").unwrap();
+        let rust_value = convert_into::<RustType>(&yaml_value).unwrap();
+        assert_eq!(rust_value.as_object().unwrap().keys().collect::<Vec<_>>(), vec!["z", "a", "m"]);
+        assert_eq!(convert_into::<serde_yaml::Value>(&rust_value).unwrap(), yaml_value);
+        assert_eq!(convert_into::<serde_json::Value>(&yaml_value).unwrap(), json!({"z": 1, "a": u64::MAX, "m": 0.5}));
+    }
+}
+
+#[cfg(all(test, feature = "trait_json"))]
+mod tests_json {
+    use super::convert;
+    use crate::rust_type_impl::RustType;
+
+    #[test]
+    fn test_convert_rust_type_to_json() {
+        let value = rust_type!({"a": [1, -2.5, "c"], "d": {"e": null, "f": false}});
+        let json_value = convert::<_, json::JsonValue>(&value).unwrap();
+        assert_eq!(json_value.dump(), r#"{"a":[1,-2.5,"c"],"d":{"e":null,"f":false}}"#);
+        assert_eq!(convert::<_, RustType>(&json_value).unwrap(), value);
+    }
+}
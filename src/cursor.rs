@@ -0,0 +1,267 @@
+// Cursor over JsonType instances that keeps track of the location (and of its ancestors) from the root.
+// The cursor allows the resolution of Relative JSON Pointers (https://tools.ietf.org/html/draft-handrews-relative-json-pointer-02)
+use crate::{
+    fragment_helpers::{array_index_from_fragment_part, fragment_components_from_fragment, fragment_from_fragment_components},
+    json_type::{JsonType, PrimitiveType},
+};
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PathComponent {
+    Attribute(String),
+    Index(usize),
+}
+
+impl fmt::Display for PathComponent {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Attribute(attribute_name) => write!(formatter, "{attribute_name}"),
+            Self::Index(index) => write!(formatter, "{index}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonCursor<'json, T: JsonType> {
+    // The first node is the root and the last one is the current location
+    nodes: Vec<&'json T>,
+    // Path from the root to the current location (nodes.len() == path.len() + 1)
+    path: Vec<PathComponent>,
+}
+
+// Manually implemented as derive(Clone) would require T: Clone
+impl<T: JsonType> Clone for JsonCursor<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            path: self.path.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RelativePointerTarget<'json, T: JsonType> {
+    Value(JsonCursor<'json, T>),
+    // Result of relative pointers terminated by `#` referencing an object attribute
+    AttributeName(String),
+    // Result of relative pointers terminated by `#` referencing an array item
+    Index(usize),
+}
+
+// Parse a non-negative integer as defined by the Relative JSON Pointer specification (no leading zeros allowed)
+fn split_non_negative_integer(value: &str) -> Option<(usize, &str)> {
+    let digits_count = value.bytes().take_while(u8::is_ascii_digit).count();
+    let (digits, rest) = value.split_at(digits_count);
+    if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+        None
+    } else {
+        digits.parse::<usize>().ok().map(|integer| (integer, rest))
+    }
+}
+
+impl<'json, T: JsonType> JsonCursor<'json, T> {
+    #[must_use]
+    pub fn new(root: &'json T) -> Self {
+        Self {
+            nodes: vec![root],
+            path: Vec::new(),
+        }
+    }
+
+    // Create a cursor pointing to the location referenced by the absolute JSON Pointer `fragment`
+    #[must_use]
+    pub fn from_fragment(root: &'json T, fragment: &str) -> Option<Self> {
+        Self::new(root).descend(fragment)
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &'json T {
+        self.nodes[0]
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &'json T {
+        self.nodes[self.nodes.len() - 1]
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &[PathComponent] {
+        &self.path
+    }
+
+    #[must_use]
+    pub const fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    // Absolute JSON Pointer of the current location
+    #[must_use]
+    pub fn fragment(&self) -> String {
+        fragment_from_fragment_components(&self.path)
+    }
+
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        self.ancestor(1)
+    }
+
+    // Cursor pointing to the `levels`-th ancestor of the current location (`ancestor(0)` is the current location)
+    #[must_use]
+    pub fn ancestor(&self, levels: usize) -> Option<Self> {
+        if levels > self.depth() {
+            None
+        } else {
+            let depth = self.depth() - levels;
+            Some(Self {
+                nodes: self.nodes[..=depth].to_vec(),
+                path: self.path[..depth].to_vec(),
+            })
+        }
+    }
+
+    // Cursor pointing to the attribute or the item (depending on the primitive type of the current value) identified by `fragment_part`
+    #[must_use]
+    pub fn child(&self, fragment_part: &str) -> Option<Self> {
+        let value = self.value();
+        let (child, path_component) = match value.try_primitive_type().ok()? {
+            PrimitiveType::Object => (value.get_attribute(fragment_part)?, PathComponent::Attribute(fragment_part.to_string())),
+            PrimitiveType::Array => {
+                let index = array_index_from_fragment_part(fragment_part)?;
+                (value.get_index(index)?, PathComponent::Index(index))
+            }
+            _ => return None,
+        };
+        let mut cursor = self.clone();
+        cursor.nodes.push(child);
+        cursor.path.push(path_component);
+        Some(cursor)
+    }
+
+    // Cursor pointing to the location referenced by `fragment`, a JSON Pointer evaluated from the current location
+    #[must_use]
+    pub fn descend(&self, fragment: &str) -> Option<Self> {
+        let mut cursor = self.clone();
+        for fragment_part in fragment_components_from_fragment(fragment) {
+            cursor = cursor.child(&fragment_part)?;
+        }
+        Some(cursor)
+    }
+
+    // Resolve `relative_pointer` (ie. `1/foo`, `0-1`, `2#`) from the current location.
+    // None is returned if the pointer is not valid or if it does not reference any location.
+    #[must_use]
+    pub fn resolve_relative_pointer(&self, relative_pointer: &str) -> Option<RelativePointerTarget<'json, T>> {
+        let (levels, rest) = split_non_negative_integer(relative_pointer)?;
+        let mut cursor = self.ancestor(levels)?;
+
+        let rest = if let Some(sign @ ('+' | '-')) = rest.chars().next() {
+            let (offset, rest) = split_non_negative_integer(&rest[1..])?;
+            let index = match cursor.path.last() {
+                Some(PathComponent::Index(index)) if sign == '+' => index.checked_add(offset)?,
+                Some(PathComponent::Index(index)) => index.checked_sub(offset)?,
+                _ => return None,
+            };
+            cursor = cursor.parent()?.child(&index.to_string())?;
+            rest
+        } else {
+            rest
+        };
+
+        if rest == "#" {
+            match cursor.path.last()? {
+                PathComponent::Attribute(attribute_name) => Some(RelativePointerTarget::AttributeName(attribute_name.clone())),
+                PathComponent::Index(index) => Some(RelativePointerTarget::Index(*index)),
+            }
+        } else if rest.is_empty() || rest.starts_with('/') {
+            cursor.descend(rest).map(RelativePointerTarget::Value)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonCursor, PathComponent, RelativePointerTarget};
+    use crate::rust_type_impl::RustType;
+    use test_case::test_case;
+
+    lazy_static! {
+        // Example from the Relative JSON Pointer specification
+        static ref DOCUMENT: RustType = rust_type!({
+            "foo": ["bar", "baz"],
+            "highly": {"nested": {"objects": true}}
+        });
+    }
+
+    // Simplified representation of RelativePointerTarget to allow comparisons
+    #[derive(Debug, PartialEq)]
+    enum Target {
+        Value(String, RustType),
+        AttributeName(String),
+        Index(usize),
+    }
+
+    #[test_case("/foo/1", "0" => Some(Target::Value("/foo/1".to_string(), rust_type!("baz"))))]
+    #[test_case("/foo/1", "1/0" => Some(Target::Value("/foo/0".to_string(), rust_type!("bar"))))]
+    #[test_case("/foo/1", "0-1" => Some(Target::Value("/foo/0".to_string(), rust_type!("bar"))))]
+    #[test_case("/foo/0", "0+1" => Some(Target::Value("/foo/1".to_string(), rust_type!("baz"))))]
+    #[test_case("/foo/1", "2/highly/nested/objects" => Some(Target::Value("/highly/nested/objects".to_string(), rust_type!(true))))]
+    #[test_case("/foo/1", "0#" => Some(Target::Index(1)))]
+    #[test_case("/foo/1", "0-1#" => Some(Target::Index(0)))]
+    #[test_case("/foo/1", "1#" => Some(Target::AttributeName("foo".to_string())))]
+    #[test_case("/highly/nested", "0/objects" => Some(Target::Value("/highly/nested/objects".to_string(), rust_type!(true))))]
+    #[test_case("/highly/nested", "1/nested/objects" => Some(Target::Value("/highly/nested/objects".to_string(), rust_type!(true))))]
+    #[test_case("/highly/nested", "2/foo/0" => Some(Target::Value("/foo/0".to_string(), rust_type!("bar"))))]
+    #[test_case("/highly/nested", "0#" => Some(Target::AttributeName("nested".to_string())))]
+    #[test_case("/highly/nested", "1#" => Some(Target::AttributeName("highly".to_string())))]
+    #[test_case("/foo/1", "3" => None ; "above the root")]
+    #[test_case("", "0#" => None ; "name of the root")]
+    #[test_case("/foo/1", "0+1" => None ; "index out of bounds")]
+    #[test_case("/foo/0", "0-1" => None ; "negative index")]
+    #[test_case("/highly/nested", "0-1" => None ; "index manipulation on object attribute")]
+    #[test_case("/foo/1", "01" => None ; "leading zero")]
+    #[test_case("/foo/1", "-1" => None ; "missing levels")]
+    #[test_case("/foo/1", "0foo" => None ; "missing slash")]
+    #[test_case("/foo/1", "1/2" => None ; "not existing location")]
+    #[test_case("/foo/1", "1/+0" => None ; "array index with plus sign")]
+    fn test_resolve_relative_pointer(fragment: &str, relative_pointer: &str) -> Option<Target> {
+        let cursor = JsonCursor::from_fragment(&*DOCUMENT, fragment).unwrap();
+        cursor.resolve_relative_pointer(relative_pointer).map(|target| match target {
+            RelativePointerTarget::Value(cursor) => Target::Value(cursor.fragment(), cursor.value().clone()),
+            RelativePointerTarget::AttributeName(attribute_name) => Target::AttributeName(attribute_name),
+            RelativePointerTarget::Index(index) => Target::Index(index),
+        })
+    }
+
+    #[test]
+    fn test_cursor_navigation() {
+        let cursor = JsonCursor::from_fragment(&*DOCUMENT, "/highly/nested/objects").unwrap();
+        assert_eq!(cursor.depth(), 3);
+        assert_eq!(
+            cursor.path(),
+            &[
+                PathComponent::Attribute("highly".to_string()),
+                PathComponent::Attribute("nested".to_string()),
+                PathComponent::Attribute("objects".to_string())
+            ]
+        );
+        assert_eq!(cursor.parent().unwrap().fragment(), "/highly/nested");
+        assert_eq!(cursor.ancestor(3).unwrap().value(), &*DOCUMENT);
+        assert!(cursor.ancestor(4).is_none());
+        assert_eq!(cursor.root(), &*DOCUMENT);
+        assert!(JsonCursor::from_fragment(&*DOCUMENT, "/foo/2").is_none());
+        assert_eq!(JsonCursor::new(&*DOCUMENT).child("foo").unwrap().child("1").unwrap().value(), &rust_type!("baz"));
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_cursor_on_serde_json() {
+        let document = json!({"items": [{"name": "a"}, {"name": "b"}]});
+        let cursor = JsonCursor::from_fragment(&document, "/items/1/name").unwrap();
+        match cursor.resolve_relative_pointer("1-1/name") {
+            Some(RelativePointerTarget::Value(sibling)) => assert_eq!(sibling.value(), &json!("a")),
+            _ => panic!("Expected a value"),
+        }
+    }
+}
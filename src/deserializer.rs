@@ -0,0 +1,430 @@
+// serde Deserializer backed by any JsonType, it allows reading user defined structs directly from the JsonType instances
+use crate::{
+    error::Error,
+    fragment_helpers::fragment_from_fragment_components,
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+};
+use serde::{
+    de::{self, value::StringDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor},
+    forward_to_deserialize_any, Deserialize,
+};
+use std::{convert::TryFrom, fmt, iter::once};
+
+// Error reported by the deserializer. The pointer is set by the deserializer of the value that has originated the error
+// as errors generated by serde Visitors (via `de::Error::custom`) are not aware of the location
+#[derive(Debug)]
+pub struct DeserializerError {
+    pointer: Option<String>,
+    reason: String,
+}
+
+impl fmt::Display for DeserializerError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.reason)
+    }
+}
+
+impl std::error::Error for DeserializerError {}
+
+impl de::Error for DeserializerError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            pointer: None,
+            reason: msg.to_string(),
+        }
+    }
+}
+
+impl From<DeserializerError> for Error {
+    fn from(error: DeserializerError) -> Self {
+        Self::DeserializationFailed {
+            pointer: error.pointer.unwrap_or_default(),
+            reason: error.reason,
+        }
+    }
+}
+
+impl DeserializerError {
+    // JSON Pointer of the value that could not be deserialized
+    #[must_use]
+    pub fn pointer(&self) -> &str {
+        self.pointer.as_deref().unwrap_or_default()
+    }
+
+    fn at(mut self, pointer: &str) -> Self {
+        if self.pointer.is_none() {
+            self.pointer = Some(pointer.to_string());
+        }
+        self
+    }
+}
+
+// serde Deserializer of `JsonType` instances, it allows the usage of `DeserializeSeed` implementations and of
+// wrappers as `serde_path_to_error`. Use `from_json_type` for the common case.
+#[derive(Debug)]
+pub struct JsonTypeDeserializer<'de, T: JsonType> {
+    value: &'de T,
+    // JSON Pointer of `value`
+    pointer: String,
+}
+
+impl<'de, T: JsonType> JsonTypeDeserializer<'de, T> {
+    #[must_use]
+    pub const fn new(value: &'de T) -> Self {
+        Self { value, pointer: String::new() }
+    }
+
+    fn child(&self, value: &'de T, fragment_part: &str) -> Self {
+        Self {
+            value,
+            pointer: format!("{}{}", self.pointer, fragment_from_fragment_components(once(fragment_part))),
+        }
+    }
+
+    fn deserialize_value<V: Visitor<'de>>(&self, visitor: V) -> Result<V::Value, DeserializerError> {
+        match self.value.try_primitive_type().map_err(de::Error::custom)? {
+            PrimitiveType::Array => visitor.visit_seq(SeqDeserializer {
+                parent: self,
+                items: self.value.as_array().map_or_else(Vec::new, Iterator::collect).into_iter().enumerate(),
+            }),
+            PrimitiveType::Boolean => visitor.visit_bool(self.value.as_boolean().unwrap_or_default()),
+            PrimitiveType::Integer => {
+                let integer = self.value.as_integer().unwrap_or_default();
+                if let Ok(integer) = i64::try_from(integer) {
+                    visitor.visit_i64(integer)
+                } else if let Ok(integer) = u64::try_from(integer) {
+                    visitor.visit_u64(integer)
+                } else {
+                    visitor.visit_i128(integer)
+                }
+            }
+            PrimitiveType::Null => visitor.visit_unit(),
+            PrimitiveType::Number => visitor.visit_f64(self.value.as_number().unwrap_or_default()),
+            PrimitiveType::Object => visitor.visit_map(MapDeserializer {
+                parent: self,
+                keys: self
+                    .value
+                    .as_object()
+                    .map(|object| object.keys().map(ToString::to_string).collect::<Vec<_>>())
+                    .unwrap_or_default()
+                    .into_iter(),
+                next_value: None,
+            }),
+            PrimitiveType::String => visitor.visit_borrowed_str(self.value.as_string().unwrap_or_default()),
+        }
+    }
+}
+
+impl<'de, T: JsonType> de::Deserializer<'de> for JsonTypeDeserializer<'de, T> {
+    type Error = DeserializerError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_value(visitor).map_err(|error| error.at(&self.pointer))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_null() {
+            visitor.visit_none().map_err(|error: Self::Error| error.at(&self.pointer))
+        } else {
+            let pointer = self.pointer.clone();
+            visitor.visit_some(self).map_err(|error| error.at(&pointer))
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        let pointer = self.pointer.clone();
+        visitor.visit_newtype_struct(self).map_err(|error| error.at(&pointer))
+    }
+
+    // Unit variants are represented as strings (`"Variant"`) and the other variants as objects with a single attribute (`{"Variant": ...}`)
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        let pointer = self.pointer.clone();
+        let single_attribute = self.value.as_object().and_then(|object| {
+            let mut keys = object.keys();
+            match (keys.next(), keys.next()) {
+                (Some(key), None) => Some(key.to_string()),
+                _ => None,
+            }
+        });
+        let result = if let Some(variant) = self.value.as_string() {
+            visitor.visit_enum(EnumDeserializer::<T> {
+                variant: variant.to_string(),
+                value: None,
+            })
+        } else if let Some((variant, value)) = single_attribute.and_then(|variant| self.value.get_attribute(&variant).map(|value| (variant, value))) {
+            visitor.visit_enum(EnumDeserializer {
+                value: Some(self.child(value, &variant)),
+                variant,
+            })
+        } else {
+            Err(match self.value.try_primitive_type() {
+                Ok(primitive_type) => de::Error::custom(format!("expected a string or an object with a single attribute, found {primitive_type}")),
+                Err(error) => de::Error::custom(error),
+            })
+        };
+        result.map_err(|error| error.at(&pointer))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqDeserializer<'parent, 'de, T: JsonType> {
+    parent: &'parent JsonTypeDeserializer<'de, T>,
+    items: std::iter::Enumerate<std::vec::IntoIter<&'de T>>,
+}
+
+impl<'de, T: JsonType> SeqAccess<'de> for SeqDeserializer<'_, 'de, T> {
+    type Error = DeserializerError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        match self.items.next() {
+            Some((index, item)) => seed.deserialize(self.parent.child(item, &index.to_string())).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapDeserializer<'parent, 'de, T: JsonType> {
+    parent: &'parent JsonTypeDeserializer<'de, T>,
+    keys: std::vec::IntoIter<String>,
+    next_value: Option<JsonTypeDeserializer<'de, T>>,
+}
+
+impl<'de, T: JsonType> MapAccess<'de> for MapDeserializer<'_, 'de, T> {
+    type Error = DeserializerError;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        for key in self.keys.by_ref() {
+            if let Some(value) = self.parent.value.get_attribute(&key) {
+                self.next_value = Some(self.parent.child(value, &key));
+                let key_deserializer: StringDeserializer<Self::Error> = key.into_deserializer();
+                return seed.deserialize(key_deserializer).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Self::Error> {
+        let value_deserializer = self.next_value.take().ok_or_else(|| de::Error::custom("value requested before its key"))?;
+        seed.deserialize(value_deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len())
+    }
+}
+
+struct EnumDeserializer<'de, T: JsonType> {
+    variant: String,
+    // Deserializer of the variant content, None for unit variants
+    value: Option<JsonTypeDeserializer<'de, T>>,
+}
+
+impl<'de, T: JsonType> EnumDeserializer<'de, T> {
+    fn content(self, expected: &'static str) -> Result<JsonTypeDeserializer<'de, T>, DeserializerError> {
+        self.value.ok_or_else(|| de::Error::invalid_type(de::Unexpected::UnitVariant, &expected))
+    }
+}
+
+impl<'de, T: JsonType> EnumAccess<'de> for EnumDeserializer<'de, T> {
+    type Error = DeserializerError;
+    type Variant = Self;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error> {
+        let variant_deserializer: StringDeserializer<Self::Error> = self.variant.clone().into_deserializer();
+        seed.deserialize(variant_deserializer).map(|variant| (variant, self))
+    }
+}
+
+impl<'de, T: JsonType> VariantAccess<'de> for EnumDeserializer<'de, T> {
+    type Error = DeserializerError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.value.map_or(Ok(()), Deserialize::deserialize)
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, Self::Error> {
+        seed.deserialize(self.content("newtype variant")?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self.content("tuple variant")?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self.content("struct variant")?, visitor)
+    }
+}
+
+/// Deserialize an instance of `D` from any `JsonType`, without serializing it into an intermediate representation.
+///
+/// # Errors
+/// `Error::DeserializationFailed` if `value` does not match the structure of `D`, the error reports the JSON Pointer
+/// of the value that could not be deserialized.
+pub fn from_json_type<'de, D: Deserialize<'de>, T: JsonType>(value: &'de T) -> Result<D, Error> {
+    D::deserialize(JsonTypeDeserializer::new(value)).map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_json_type, JsonTypeDeserializer};
+    use crate::{error::Error, rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use serde::{de::DeserializeSeed, Deserialize};
+    use std::{collections::HashMap, marker::PhantomData};
+    use test_case::test_case;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Protocol {
+        Http,
+        Https { verify: bool },
+        Custom(String),
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+        protocol: Protocol,
+        #[serde(default)]
+        aliases: Vec<String>,
+        timeout: Option<f64>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        name: String,
+        servers: Vec<Server>,
+        labels: HashMap<String, String>,
+    }
+
+    #[test]
+    fn test_from_json_type() {
+        let value = rust_type!({
+            "name": "config",
+            "servers": [
+                {"host": "a", "port": 80, "protocol": "Http", "timeout": null},
+                {"host": "b", "port": 443, "protocol": {"Https": {"verify": true}}, "aliases": ["c"], "timeout": 1.5},
+                {"host": "d", "port": 8080, "protocol": {"Custom": "grpc"}},
+            ],
+            "labels": {"env": "test"},
+        });
+        assert_eq!(
+            from_json_type::<Config, _>(&value).unwrap(),
+            Config {
+                name: "config".to_string(),
+                servers: vec![
+                    Server {
+                        host: "a".to_string(),
+                        port: 80,
+                        protocol: Protocol::Http,
+                        aliases: vec![],
+                        timeout: None,
+                    },
+                    Server {
+                        host: "b".to_string(),
+                        port: 443,
+                        protocol: Protocol::Https { verify: true },
+                        aliases: vec!["c".to_string()],
+                        timeout: Some(1.5),
+                    },
+                    Server {
+                        host: "d".to_string(),
+                        port: 8080,
+                        protocol: Protocol::Custom("grpc".to_string()),
+                        aliases: vec![],
+                        timeout: None,
+                    },
+                ],
+                labels: vec![("env".to_string(), "test".to_string())].into_iter().collect(),
+            }
+        );
+    }
+
+    #[test_case(&rust_type!({"name": "n", "servers": [], "labels": {"a": 1}}), "/labels/a" ; "wrong type")]
+    #[test_case(&rust_type!({"name": "n", "servers": [{"host": "a", "port": 70000, "protocol": "Http"}], "labels": {}}), "/servers/0/port" ; "out of range")]
+    #[test_case(&rust_type!({"name": "n", "servers": [{"host": "a", "protocol": "Http"}], "labels": {}}), "/servers/0" ; "missing field")]
+    #[test_case(&rust_type!({"name": "n", "servers": [{"host": "a", "port": 1, "protocol": "Ftp"}], "labels": {}}), "/servers/0/protocol" ; "unknown variant")]
+    #[test_case(&rust_type!({"name": "n", "servers": [{"host": "a", "port": 1, "protocol": {"Https": {}}}], "labels": {}}), "/servers/0/protocol/Https" ; "invalid variant content")]
+    #[test_case(&rust_type!([]), "" ; "root")]
+    fn test_from_json_type_error_pointer(value: &RustType, expected_pointer: &str) {
+        match from_json_type::<Config, _>(value) {
+            Err(Error::DeserializationFailed { pointer, .. }) => assert_eq!(pointer, expected_pointer),
+            result => panic!("Expected DeserializationFailed error, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_from_json_type_error_message() {
+        assert_eq!(
+            from_json_type::<Config, _>(&rust_type!({"name": 1})).unwrap_err().to_string(),
+            "Unable to deserialize the value referenced by `/name`: invalid type: integer `1`, expected a string"
+        );
+    }
+
+    #[test]
+    fn test_from_json_type_unrecognized_value() {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        assert_eq!(
+            from_json_type::<Vec<i64>, _>(&value),
+            Err(Error::DeserializationFailed {
+                pointer: "/1".to_string(),
+                reason: "The value does not match any JSON primitive type".to_string(),
+            })
+        );
+        // Enums are not deserialized via `deserialize_any`
+        assert_eq!(
+            from_json_type::<Vec<Protocol>, _>(&PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Unrecognized])),
+            Err(Error::DeserializationFailed {
+                pointer: "/0".to_string(),
+                reason: "The value does not match any JSON primitive type".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_deserialize_seed() {
+        let value = rust_type!({"host": "a", "port": "80", "protocol": "Http"});
+        let error = PhantomData::<Server>.deserialize(JsonTypeDeserializer::new(&value)).unwrap_err();
+        assert_eq!(error.pointer(), "/port");
+        assert_eq!(error.to_string(), "invalid type: string \"80\", expected u16");
+    }
+
+    #[test]
+    fn test_from_json_type_into_rust_type() {
+        let value = rust_type!({"a": [1, 2.5, null, "b"], "c": {"d": true}});
+        assert_eq!(from_json_type::<RustType, _>(&value).unwrap(), value);
+    }
+
+    #[test]
+    fn test_from_json_type_borrows_strings() {
+        let value = rust_type!(["a", "b"]);
+        assert_eq!(from_json_type::<Vec<&str>, _>(&value).unwrap(), vec!["a", "b"]);
+    }
+
+    #[cfg(feature = "trait_serde_yaml")]
+    #[test]
+    fn test_from_serde_yaml() {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str("name: yaml\nservers: []\nlabels: {team: ops}").unwrap();
+        let config = from_json_type::<Config, _>(&yaml_value).unwrap();
+        assert_eq!(config.name, "yaml");
+        assert_eq!(config.labels.get("team").map(String::as_str), Some("ops"));
+    }
+
+    #[cfg(feature = "trait_json")]
+    #[test]
+    fn test_from_json() {
+        let json_value = json::parse(r#"{"host": "h", "port": 1, "protocol": "Http", "timeout": 2}"#).unwrap();
+        assert_eq!(from_json_type::<Server, _>(&json_value).unwrap().timeout, Some(2.0));
+    }
+}
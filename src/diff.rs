@@ -0,0 +1,241 @@
+// Structural diff of JsonType instances, the output is a list of JSON Patch (RFC 6902) operations
+use crate::{
+    comparison::{json_eq_with, NumericComparison},
+    fragment_helpers::fragment_from_fragment_components,
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+    patch::PatchOperation,
+    rust_type_impl::RustType,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ArrayDiffStrategy {
+    // Compare items with the same index, additions and removals happen only at the end of the array
+    #[default]
+    IndexByIndex,
+    // Preserve the longest common subsequence of items and add/remove the remaining ones
+    LongestCommonSubsequence,
+}
+
+// Strict numeric comparison guarantees that applying the generated patch reproduces exactly the target value
+fn is_equal<A: JsonType, B: JsonType>(a: &A, b: &B) -> bool {
+    json_eq_with(a, b, NumericComparison::Strict)
+}
+
+struct Differ {
+    array_diff_strategy: ArrayDiffStrategy,
+    fragment_components: Vec<String>,
+    operations: Vec<PatchOperation<RustType>>,
+}
+
+impl Differ {
+    fn current_path(&self) -> String {
+        fragment_from_fragment_components(&self.fragment_components)
+    }
+
+    fn add<B: JsonType>(&mut self, value: &B) {
+        let path = self.current_path();
+        self.operations.push(PatchOperation::Add {
+            path,
+            value: value.to_rust_type(),
+        });
+    }
+
+    fn remove(&mut self) {
+        let path = self.current_path();
+        self.operations.push(PatchOperation::Remove { path });
+    }
+
+    fn replace<B: JsonType>(&mut self, value: &B) {
+        let path = self.current_path();
+        self.operations.push(PatchOperation::Replace {
+            path,
+            value: value.to_rust_type(),
+        });
+    }
+
+    fn with_fragment_component(&mut self, fragment_component: String, action: impl FnOnce(&mut Self)) {
+        self.fragment_components.push(fragment_component);
+        action(self);
+        let _ = self.fragment_components.pop();
+    }
+
+    fn diff<A: JsonType, B: JsonType>(&mut self, a: &A, b: &B) {
+        match (a.primitive_type(), b.primitive_type()) {
+            (PrimitiveType::Object, PrimitiveType::Object) => self.diff_objects(a, b),
+            (PrimitiveType::Array, PrimitiveType::Array) => match self.array_diff_strategy {
+                ArrayDiffStrategy::IndexByIndex => self.diff_arrays_index_by_index(a, b),
+                ArrayDiffStrategy::LongestCommonSubsequence => self.diff_arrays_longest_common_subsequence(a, b),
+            },
+            _ => {
+                if !is_equal(a, b) {
+                    self.replace(b);
+                }
+            }
+        }
+    }
+
+    fn diff_objects<A: JsonType, B: JsonType>(&mut self, a: &A, b: &B) {
+        if let (Some(a_object), Some(b_object)) = (a.as_object(), b.as_object()) {
+            for (key, a_value) in a_object.items() {
+                self.with_fragment_component(key.to_string(), |differ| match b.get_attribute(key) {
+                    Some(b_value) => differ.diff(a_value, b_value),
+                    None => differ.remove(),
+                });
+            }
+            for (key, b_value) in b_object.items() {
+                if !a.has_attribute(key) {
+                    self.with_fragment_component(key.to_string(), |differ| differ.add(b_value));
+                }
+            }
+        }
+    }
+
+    fn diff_arrays_index_by_index<A: JsonType, B: JsonType>(&mut self, a: &A, b: &B) {
+        if let (Some(a_items), Some(b_items)) = (a.as_array(), b.as_array()) {
+            let a_items = a_items.collect::<Vec<_>>();
+            let b_items = b_items.collect::<Vec<_>>();
+            for (index, (a_item, b_item)) in a_items.iter().zip(b_items.iter()).enumerate() {
+                self.with_fragment_component(index.to_string(), |differ| differ.diff(*a_item, *b_item));
+            }
+            for (index, b_item) in b_items.iter().enumerate().skip(a_items.len()) {
+                self.with_fragment_component(index.to_string(), |differ| differ.add(*b_item));
+            }
+            // Removals start from the end of the array, so indexes of the items still to be removed are not shifted
+            for index in (b_items.len()..a_items.len()).rev() {
+                self.with_fragment_component(index.to_string(), Self::remove);
+            }
+        }
+    }
+
+    fn diff_arrays_longest_common_subsequence<A: JsonType, B: JsonType>(&mut self, a: &A, b: &B) {
+        if let (Some(a_items), Some(b_items)) = (a.as_array(), b.as_array()) {
+            let a_items = a_items.collect::<Vec<_>>();
+            let b_items = b_items.collect::<Vec<_>>();
+
+            // lcs_lengths[i][j] is the length of the longest common subsequence of a_items[i..] and b_items[j..]
+            let mut lcs_lengths = vec![vec![0_usize; b_items.len() + 1]; a_items.len() + 1];
+            for a_index in (0..a_items.len()).rev() {
+                for b_index in (0..b_items.len()).rev() {
+                    lcs_lengths[a_index][b_index] = if is_equal(a_items[a_index], b_items[b_index]) {
+                        lcs_lengths[a_index + 1][b_index + 1] + 1
+                    } else {
+                        lcs_lengths[a_index + 1][b_index].max(lcs_lengths[a_index][b_index + 1])
+                    };
+                }
+            }
+
+            // current_index tracks the position on the array while the previous operations are applied
+            let (mut a_index, mut b_index, mut current_index) = (0, 0, 0);
+            while a_index < a_items.len() || b_index < b_items.len() {
+                if a_index < a_items.len() && b_index < b_items.len() && is_equal(a_items[a_index], b_items[b_index]) {
+                    a_index += 1;
+                    b_index += 1;
+                    current_index += 1;
+                } else if b_index < b_items.len() && (a_index == a_items.len() || lcs_lengths[a_index][b_index + 1] >= lcs_lengths[a_index + 1][b_index]) {
+                    if a_index < a_items.len() && lcs_lengths[a_index][b_index + 1] == lcs_lengths[a_index + 1][b_index + 1] {
+                        // Both items are not part of the subsequence, so the item could be modified in-place
+                        self.with_fragment_component(current_index.to_string(), |differ| differ.diff(a_items[a_index], b_items[b_index]));
+                        a_index += 1;
+                    } else {
+                        self.with_fragment_component(current_index.to_string(), |differ| differ.add(b_items[b_index]));
+                    }
+                    b_index += 1;
+                    current_index += 1;
+                } else {
+                    self.with_fragment_component(current_index.to_string(), Self::remove);
+                    a_index += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Generate the JSON Patch operations needed to transform `a` into `b`
+///
+/// # Panics
+/// The function panics if `a` or `b` contain values not recognized as JSON primitive types (ie. Python objects
+/// without a JSON representation), as the content of the generated operations could not be determined.
+#[must_use]
+pub fn diff<A: JsonType, B: JsonType>(a: &A, b: &B) -> Vec<PatchOperation<RustType>> {
+    diff_with_array_strategy(a, b, ArrayDiffStrategy::default())
+}
+
+/// # Panics
+/// See `diff`
+#[must_use]
+pub fn diff_with_array_strategy<A: JsonType, B: JsonType>(a: &A, b: &B, array_diff_strategy: ArrayDiffStrategy) -> Vec<PatchOperation<RustType>> {
+    let mut differ = Differ {
+        array_diff_strategy,
+        fragment_components: Vec::new(),
+        operations: Vec::new(),
+    };
+    differ.diff(a, b);
+    differ.operations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, diff_with_array_strategy, ArrayDiffStrategy};
+    use crate::{
+        patch::{apply_patch, PatchOperation},
+        rust_type_impl::RustType,
+    };
+    use test_case::test_case;
+
+    #[test_case(&rust_type!({"a": 1}), &rust_type!({"a": 1}) => Vec::<PatchOperation<RustType>>::new() ; "equal objects")]
+    #[test_case(&rust_type!(1), &rust_type!(1.0) => vec![PatchOperation::Replace { path: String::new(), value: rust_type!(1.0) }] ; "different primitive types")]
+    #[test_case(&rust_type!({"a": 1}), &rust_type!({"b": [2]}) => vec![
+        PatchOperation::Remove { path: "/a".to_string() },
+        PatchOperation::Add { path: "/b".to_string(), value: rust_type!([2]) },
+    ] ; "object keys")]
+    #[test_case(&rust_type!({"a/b": {"c": "d"}}), &rust_type!({"a/b": {"c": "e"}}) => vec![
+        PatchOperation::Replace { path: "/a~1b/c".to_string(), value: rust_type!("e") },
+    ] ; "nested objects")]
+    #[test_case(&rust_type!([1, 2, 3]), &rust_type!([1, 4]) => vec![
+        PatchOperation::Replace { path: "/1".to_string(), value: rust_type!(4) },
+        PatchOperation::Remove { path: "/2".to_string() },
+    ] ; "shorter array")]
+    #[test_case(&rust_type!([1]), &rust_type!([1, 2, 3]) => vec![
+        PatchOperation::Add { path: "/1".to_string(), value: rust_type!(2) },
+        PatchOperation::Add { path: "/2".to_string(), value: rust_type!(3) },
+    ] ; "longer array")]
+    fn test_diff(a: &RustType, b: &RustType) -> Vec<PatchOperation<RustType>> {
+        diff(a, b)
+    }
+
+    #[test_case(&rust_type!([1, 2, 3]), &rust_type!([0, 1, 2, 3]) => vec![
+        PatchOperation::Add { path: "/0".to_string(), value: rust_type!(0) },
+    ] ; "prepend")]
+    #[test_case(&rust_type!([1, 2, 3]), &rust_type!([1, 3]) => vec![
+        PatchOperation::Remove { path: "/1".to_string() },
+    ] ; "remove in the middle")]
+    #[test_case(&rust_type!([1, {"a": 1}, 3]), &rust_type!([1, {"a": 2}, 3]) => vec![
+        PatchOperation::Replace { path: "/1/a".to_string(), value: rust_type!(2) },
+    ] ; "modify in place")]
+    fn test_diff_longest_common_subsequence(a: &RustType, b: &RustType) -> Vec<PatchOperation<RustType>> {
+        diff_with_array_strategy(a, b, ArrayDiffStrategy::LongestCommonSubsequence)
+    }
+
+    #[test_case(&rust_type!({"a": [1, 2, 3, {"b": null}], "c": "d"}), &rust_type!({"a": [0, 2, {"b": true}, 3, 4], "e": {}}))]
+    #[test_case(&rust_type!([[1, 2], [3], 4, 5]), &rust_type!([4, [1], [3, 2], 6]))]
+    #[test_case(&rust_type!({"a": 1}), &rust_type!([1]))]
+    fn test_diff_produces_applicable_patches(a: &RustType, b: &RustType) {
+        for array_diff_strategy in &[ArrayDiffStrategy::IndexByIndex, ArrayDiffStrategy::LongestCommonSubsequence] {
+            let mut patched = a.clone();
+            apply_patch(&mut patched, &diff_with_array_strategy(a, b, *array_diff_strategy)).unwrap();
+            assert_eq!(&patched, b);
+        }
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_diff_between_different_types() {
+        use crate::json_type::ToRustType;
+
+        let a = rust_type!({"a": [1, 2], "b": "c"});
+        let b = json!({"a": [1], "b": "d"});
+        let mut patched = a.clone();
+        apply_patch(&mut patched, &diff(&a, &b)).unwrap();
+        assert_eq!(patched, b.to_rust_type());
+    }
+}
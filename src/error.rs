@@ -1,6 +1,90 @@
+use crate::json_type::PrimitiveType;
 use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerErrorReason {
+    // The object does not contain the referenced attribute
+    MissingAttribute,
+    // The component referencing an array item is not a valid index
+    InvalidArrayIndex,
+    IndexOutOfBounds { length: usize },
+    // The component references an attribute or an item of a value that is neither an object nor an array
+    NotAContainer,
+}
+
+impl std::fmt::Display for PointerErrorReason {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::MissingAttribute => write!(formatter, "is not an existing attribute"),
+            Self::InvalidArrayIndex => write!(formatter, "is not a valid array index"),
+            Self::IndexOutOfBounds { length } => write!(formatter, "is out of bounds for an array of length {length}"),
+            Self::NotAContainer => write!(formatter, "cannot be resolved on a value that is neither an object nor an array"),
+        }
+    }
+}
+
+// Details about the failed resolution of a JSON Pointer (RFC 6901)
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("Component #{component_index} (`{component}`) of `{fragment}` {reason} (resolved fragment `{resolved_fragment}` is of type `{primitive_type}`)")]
+pub struct PointerError {
+    pub fragment: String,
+    // Index of the failing component (0-based)
+    pub component_index: usize,
+    // Failing component (unescaped)
+    pub component: String,
+    // Fragment that was successfully resolved before the failing component
+    pub resolved_fragment: String,
+    // Primitive type of the value referenced by `resolved_fragment`
+    pub primitive_type: PrimitiveType,
+    pub reason: PointerErrorReason,
+}
 #[derive(Debug, Error, PartialEq)]
 pub enum Error {
     #[error("Unsupported primitive type `{type_str}`. Available types are defined by `json_trait_rs::PrimitiveType::VARIANTS`")]
     UnsupportedPrimitiveType { type_str: String },
+    #[error("Expected a value of primitive type `{expected}`, found `{actual}`")]
+    UnexpectedPrimitiveType { expected: PrimitiveType, actual: PrimitiveType },
+    #[error("Index {index} is out of bounds for an array of length {length}")]
+    IndexOutOfBounds { index: usize, length: usize },
+    #[error("Fragment `{fragment}` does not reference any value")]
+    FragmentNotFound { fragment: String },
+    #[error("Invalid JSON Patch operation #{index}: {reason}")]
+    InvalidPatchOperation { index: usize, reason: String },
+    #[error("The tested value is different from the expected one")]
+    PatchTestFailed,
+    #[error("JSON Patch operation #{index} (`{operation}` on `{pointer}`) failed: {reason}")]
+    PatchOperationFailed {
+        index: usize,
+        operation: &'static str,
+        pointer: String,
+        reason: Box<Self>,
+    },
+    #[error("`{value}` cannot be represented in JSON")]
+    NonFiniteNumber { value: String },
+    #[error("Unable to write the JSON representation: {reason}")]
+    WriteFailed { reason: String },
+    #[error("Invalid JSON at line {line} column {column}: {reason}")]
+    InvalidJson { line: usize, column: usize, reason: String },
+    #[error("Maximum nesting depth ({max_depth}) exceeded at line {line} column {column}")]
+    MaxDepthExceeded { line: usize, column: usize, max_depth: usize },
+    #[error("Invalid JSONPath query at position {position}: {reason}")]
+    InvalidJsonPath { position: usize, reason: String },
+    #[error("Integer `{value}` cannot be represented by the target type")]
+    IntegerOutOfRange { value: i128 },
+    #[error("Mapping key `{key}` is not a string")]
+    NonStringKey { key: String },
+    #[error("Unable to deserialize the value referenced by `{pointer}`: {reason}")]
+    DeserializationFailed { pointer: String, reason: String },
+    #[error("Invalid JSON Schema at `{pointer}`: {reason}")]
+    InvalidSchema { pointer: String, reason: String },
+    #[error(transparent)]
+    InvalidPointer(#[from] PointerError),
+    // The backend value is not representable as any JSON primitive type (ie. a Python `set`)
+    #[error("The value does not match any JSON primitive type")]
+    UnrecognizedValue,
+    // Exception raised by the runtime backing the values (ie. a Python exception while accessing a `dict`)
+    #[error("{language} exception: {message}")]
+    ForeignException { language: &'static str, message: String },
+    #[error("Unable to access `{pointer}`: {reason}")]
+    TypedAccessFailed { pointer: String, reason: Box<Self> },
 }
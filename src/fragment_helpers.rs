@@ -22,9 +22,20 @@ pub fn fragment_from_fragment_components<I: IntoIterator<Item = T>, T: ToString>
         })
 }
 
+// Array index of a JSON Pointer component, as defined by RFC 6901 it is made of digits only and has no leading zeros
+#[must_use]
+pub fn array_index_from_fragment_part(fragment_part: &str) -> Option<usize> {
+    let is_valid_index = !fragment_part.is_empty() && fragment_part.bytes().all(|byte| byte.is_ascii_digit()) && (fragment_part == "0" || !fragment_part.starts_with('0'));
+    if is_valid_index {
+        fragment_part.parse::<usize>().ok()
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{fragment_components_from_fragment, fragment_from_fragment_components};
+    use super::{array_index_from_fragment_part, fragment_components_from_fragment, fragment_from_fragment_components};
     use test_case::test_case;
 
     #[test_case(""       => Vec::<String>::new() ; "empty")]
@@ -44,4 +55,16 @@ mod tests {
     fn test_fragment_from_fragment_components(fragment_components: Vec<&str>) -> String {
         fragment_from_fragment_components(fragment_components)
     }
+
+    #[test_case("0"   => Some(0)  ; "zero")]
+    #[test_case("10"  => Some(10) ; "multiple digits")]
+    #[test_case(""    => None     ; "empty")]
+    #[test_case("01"  => None     ; "leading zero")]
+    #[test_case("+1"  => None     ; "plus sign")]
+    #[test_case("-1"  => None     ; "minus sign")]
+    #[test_case("-"   => None     ; "past the end")]
+    #[test_case("1e1" => None     ; "exponent")]
+    fn test_array_index_from_fragment_part(fragment_part: &str) -> Option<usize> {
+        array_index_from_fragment_part(fragment_part)
+    }
 }
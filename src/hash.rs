@@ -0,0 +1,154 @@
+// Structural hashing of JsonType instances, possibly backed by different types.
+// The hash is consistent with `comparison::json_eq` (so equal values have equal hashes), in particular:
+// * object attributes are hashed in key order, so the hash does not depend on the attributes order of the backend
+// * numbers with an integral value are hashed as integers (ie. `1` and `1.0` have the same hash)
+// NOTE: The hash is not consistent with `comparison::json_eq_with` using `NumericComparison::Strict` or `NumericComparison::Epsilon`
+use crate::{
+    comparison::json_eq,
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+};
+use std::hash::{Hash, Hasher};
+
+// Hash `f64` instances consistently with `comparison::json_eq`
+#[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+fn hash_number<H: Hasher>(number: f64, hasher: &mut H) {
+    // 2^127 is exactly representable as f64 and it is the first value out of the i128 range
+    let i128_upper_bound = 2_f64.powi(127);
+    if number.trunc() == number && -i128_upper_bound <= number && number < i128_upper_bound {
+        (number as i128).hash(hasher);
+    } else if number.is_nan() {
+        // All the NaN values are equal to each other, so they should have the same hash
+        f64::NAN.to_bits().hash(hasher);
+    } else {
+        number.to_bits().hash(hasher);
+    }
+}
+
+/// # Panics
+/// The function panics if `json_object`, or any of its descendants, is not recognized as a JSON primitive type
+pub fn json_hash<T: JsonType, H: Hasher>(json_object: &T, hasher: &mut H) {
+    let primitive_type = json_object.primitive_type();
+    // Integers and numbers share the same discriminant as they are compared by value
+    let discriminant: u8 = match primitive_type {
+        PrimitiveType::Null => 0,
+        PrimitiveType::Boolean => 1,
+        PrimitiveType::Integer | PrimitiveType::Number => 2,
+        PrimitiveType::String => 3,
+        PrimitiveType::Array => 4,
+        PrimitiveType::Object => 5,
+    };
+    discriminant.hash(hasher);
+
+    match primitive_type {
+        PrimitiveType::Null => {}
+        PrimitiveType::Boolean => {
+            if let Some(boolean) = json_object.as_boolean() {
+                boolean.hash(hasher);
+            }
+        }
+        PrimitiveType::Integer => {
+            if let Some(integer) = json_object.as_integer() {
+                integer.hash(hasher);
+            }
+        }
+        PrimitiveType::Number => {
+            if let Some(number) = json_object.as_number() {
+                hash_number(number, hasher);
+            }
+        }
+        PrimitiveType::String => {
+            if let Some(string) = json_object.as_string() {
+                string.hash(hasher);
+            }
+        }
+        PrimitiveType::Array => {
+            if let Some(items) = json_object.as_array() {
+                items.len().hash(hasher);
+                for item in items {
+                    json_hash(item, hasher);
+                }
+            }
+        }
+        PrimitiveType::Object => {
+            if let Some(object) = json_object.as_object() {
+                let mut items = object.items().collect::<Vec<_>>();
+                items.sort_by_key(|(key, _)| *key);
+                items.len().hash(hasher);
+                for (key, value) in items {
+                    key.hash(hasher);
+                    json_hash(value, hasher);
+                }
+            }
+        }
+    }
+}
+
+// Wrapper that allows JsonType instances to be used as keys of HashMap and HashSet.
+// Hashing and equality are defined by `json_hash` and `comparison::json_eq`.
+#[derive(Debug)]
+pub struct JsonHashKey<'json, T: JsonType>(pub &'json T);
+
+impl<T: JsonType> Hash for JsonHashKey<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        json_hash(self.0, state);
+    }
+}
+
+impl<T: JsonType> PartialEq for JsonHashKey<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        json_eq(self.0, other.0)
+    }
+}
+
+impl<T: JsonType> Eq for JsonHashKey<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_hash, JsonHashKey};
+    use crate::{json_type::JsonType, rust_type_impl::RustType};
+    use std::{
+        collections::{hash_map::DefaultHasher, HashSet},
+        hash::Hasher,
+    };
+    use test_case::test_case;
+
+    fn hash<T: JsonType>(json_object: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        json_hash(json_object, &mut hasher);
+        hasher.finish()
+    }
+
+    #[test_case(&rust_type!(1), &rust_type!(1.0) ; "integer and integral number")]
+    #[test_case(&rust_type!(-0.0), &rust_type!(0) ; "negative zero")]
+    #[test_case(&rust_type!(f64::NAN), &rust_type!(-f64::NAN) ; "nan")]
+    #[test_case(&rust_type!({"a": 1, "b": [2, {"c": null}]}), &rust_type!({"b": [2.0, {"c": null}], "a": 1}) ; "objects with different attributes order")]
+    fn test_equal_values_have_equal_hashes(a: &RustType, b: &RustType) {
+        assert_eq!(hash(a), hash(b));
+    }
+
+    #[test_case(&rust_type!(1), &rust_type!(1.5) ; "different numbers")]
+    #[test_case(&rust_type!(1), &rust_type!("1") ; "different types")]
+    #[test_case(&rust_type!(null), &rust_type!(false) ; "null and false")]
+    #[test_case(&rust_type!([[1], 2]), &rust_type!([[1, 2]]) ; "nested arrays")]
+    #[test_case(&rust_type!({"a": "b"}), &rust_type!({"b": "a"}) ; "swapped keys and values")]
+    fn test_different_values_have_different_hashes(a: &RustType, b: &RustType) {
+        assert_ne!(hash(a), hash(b));
+    }
+
+    #[test]
+    fn test_json_hash_key() {
+        let values = [rust_type!({"a": [1, 2]}), rust_type!({"a": [1.0, 2.0]}), rust_type!({"a": [2, 1]})];
+        let unique_values = values.iter().map(JsonHashKey).collect::<HashSet<_>>();
+        assert_eq!(unique_values.len(), 2);
+    }
+
+    #[cfg(all(feature = "trait_serde_json", feature = "trait_serde_yaml", feature = "trait_json"))]
+    #[test]
+    fn test_hash_is_backend_independent() {
+        let json_value = json!({"a": [1, 2.5, null, true], "b": {"c": "d"}});
+        let expected_hash = hash(&rust_type!({"b": {"c": "d"}, "a": [1, 2.5, null, true]}));
+        assert_eq!(hash(&json_value), expected_hash);
+        assert_eq!(hash(&serde_yaml::from_str::<serde_yaml::Value>(&json_value.to_string()).unwrap()), expected_hash);
+        assert_eq!(hash(&json::parse(&json_value.to_string()).unwrap()), expected_hash);
+    }
+}
@@ -0,0 +1,1036 @@
+// JSONPath (RFC 9535: https://www.rfc-editor.org/rfc/rfc9535) query engine on top of JsonType trait.
+// NOTE: The `match` and `search` function extensions are not supported as they require an I-Regexp (RFC 9485) engine
+use crate::{
+    comparison::{json_cmp, json_eq},
+    cursor::PathComponent,
+    error::Error,
+    fragment_helpers::fragment_from_fragment_components,
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+    parser::parse_json,
+    rust_type_impl::RustType,
+};
+use std::{cmp::Ordering, convert::TryFrom, fmt::Write, str::FromStr};
+
+// Integers in JSONPath queries are limited to the I-JSON (RFC 7493) range
+const MAX_INTEGER: i64 = (1 << 53) - 1;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice { start: Option<i64>, end: Option<i64>, step: Option<i64> },
+    Filter(LogicalExpression),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Child(Vec<Selector>),
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Query {
+    // Relative queries (`@`) are evaluated from the current node of the filter expression
+    is_relative: bool,
+    segments: Vec<Segment>,
+}
+
+impl Query {
+    // Singular queries reference at most one node
+    fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| match segment {
+            Segment::Child(selectors) => selectors.len() == 1 && matches!(selectors[0], Selector::Name(_) | Selector::Index(_)),
+            Segment::Descendant(_) => false,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Comparable {
+    Literal(RustType),
+    SingularQuery(Query),
+    Function(Function),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Function {
+    Length(Box<Comparable>),
+    Count(Query),
+    Value(Query),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum LogicalExpression {
+    Or(Vec<Self>),
+    And(Vec<Self>),
+    Not(Box<Self>),
+    Exists(Query),
+    Comparison(Comparable, ComparisonOperator, Comparable),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonPath {
+    query: Query,
+}
+
+#[derive(Debug)]
+pub struct JsonPathMatch<'json, T: JsonType> {
+    pub value: &'json T,
+    pub path: Vec<PathComponent>,
+}
+
+impl<T: JsonType> JsonPathMatch<'_, T> {
+    // Normalized path (RFC 9535 Section 2.7) of the matched node (ie. `$['a'][0]`)
+    #[must_use]
+    pub fn normalized_path(&self) -> String {
+        let mut result = "$".to_string();
+        for path_component in &self.path {
+            match path_component {
+                PathComponent::Index(index) => {
+                    let _ = write!(result, "[{index}]");
+                }
+                PathComponent::Attribute(attribute_name) => {
+                    result.push_str("['");
+                    for character in attribute_name.chars() {
+                        match character {
+                            '\u{8}' => result.push_str("\\b"),
+                            '\u{c}' => result.push_str("\\f"),
+                            '\n' => result.push_str("\\n"),
+                            '\r' => result.push_str("\\r"),
+                            '\t' => result.push_str("\\t"),
+                            '\'' => result.push_str("\\'"),
+                            '\\' => result.push_str("\\\\"),
+                            character if character < ' ' => {
+                                let _ = write!(result, "\\u{:04x}", character as u32);
+                            }
+                            character => result.push(character),
+                        }
+                    }
+                    result.push_str("']");
+                }
+            }
+        }
+        result
+    }
+
+    // JSON Pointer (RFC 6901) of the matched node
+    #[must_use]
+    pub fn fragment(&self) -> String {
+        fragment_from_fragment_components(&self.path)
+    }
+}
+
+struct QueryParser<'query> {
+    query: &'query str,
+    position: usize,
+}
+
+impl QueryParser<'_> {
+    fn error(&self, reason: &str) -> Error {
+        Error::InvalidJsonPath {
+            position: self.query[..self.position].chars().count(),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.query[self.position..].chars().next()
+    }
+
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.query[self.position..].starts_with(prefix)
+    }
+
+    fn consume(&mut self, prefix: &str) -> bool {
+        if self.starts_with(prefix) {
+            self.position += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, prefix: &str) -> Result<(), Error> {
+        if self.consume(prefix) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{prefix}`")))
+        }
+    }
+
+    fn skip_blanks(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<Query, Error> {
+        let is_relative = match self.peek() {
+            Some('$') => false,
+            Some('@') => true,
+            _ => return Err(self.error("expected `$` or `@`")),
+        };
+        self.position += 1;
+        let mut segments = Vec::new();
+        loop {
+            // Blanks are allowed before each segment, but they are not part of the query if no segment follows
+            let position_before_blanks = self.position;
+            self.skip_blanks();
+            if self.consume("..") {
+                segments.push(Segment::Descendant(if self.starts_with("[") {
+                    self.parse_bracketed_selection()?
+                } else if self.consume("*") {
+                    vec![Selector::Wildcard]
+                } else {
+                    vec![Selector::Name(self.parse_member_name_shorthand()?)]
+                }));
+            } else if self.consume(".") {
+                segments.push(Segment::Child(if self.consume("*") {
+                    vec![Selector::Wildcard]
+                } else {
+                    vec![Selector::Name(self.parse_member_name_shorthand()?)]
+                }));
+            } else if self.starts_with("[") {
+                segments.push(Segment::Child(self.parse_bracketed_selection()?));
+            } else {
+                self.position = position_before_blanks;
+                return Ok(Query { is_relative, segments });
+            }
+        }
+    }
+
+    fn parse_member_name_shorthand(&mut self) -> Result<String, Error> {
+        let start = self.position;
+        while let Some(character) = self.peek() {
+            let is_name_first = character.is_ascii_alphabetic() || character == '_' || !character.is_ascii();
+            if is_name_first || (self.position > start && character.is_ascii_digit()) {
+                self.position += character.len_utf8();
+            } else {
+                break;
+            }
+        }
+        if self.position == start {
+            Err(self.error("expected a member name"))
+        } else {
+            Ok(self.query[start..self.position].to_string())
+        }
+    }
+
+    fn parse_bracketed_selection(&mut self) -> Result<Vec<Selector>, Error> {
+        self.expect("[")?;
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_blanks();
+            selectors.push(self.parse_selector()?);
+            self.skip_blanks();
+            if self.consume("]") {
+                return Ok(selectors);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, Error> {
+        match self.peek() {
+            Some('\'' | '"') => Ok(Selector::Name(self.parse_string_literal()?)),
+            Some('*') => {
+                self.position += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.position += 1;
+                self.skip_blanks();
+                Ok(Selector::Filter(self.parse_logical_or()?))
+            }
+            _ => {
+                let start = if self.starts_with(":") { None } else { Some(self.parse_integer()?) };
+                self.skip_blanks();
+                if !self.consume(":") {
+                    return start.map(Selector::Index).ok_or_else(|| self.error("expected a selector"));
+                }
+                self.skip_blanks();
+                let end = if let Some('-' | '0'..='9') = self.peek() { Some(self.parse_integer()?) } else { None };
+                self.skip_blanks();
+                let step = if self.consume(":") {
+                    self.skip_blanks();
+                    if let Some('-' | '0'..='9') = self.peek() {
+                        Some(self.parse_integer()?)
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+                Ok(Selector::Slice { start, end, step })
+            }
+        }
+    }
+
+    fn parse_integer(&mut self) -> Result<i64, Error> {
+        let start = self.position;
+        let _ = self.consume("-");
+        let digits_start = self.position;
+        while let Some('0'..='9') = self.peek() {
+            self.position += 1;
+        }
+        let digits = &self.query[digits_start..self.position];
+        if digits.is_empty() {
+            self.position = start;
+            return Err(self.error("expected an integer"));
+        }
+        if digits.starts_with('0') && (digits.len() > 1 || digits_start > start) {
+            self.position = start;
+            return Err(self.error("integers cannot have leading zeros"));
+        }
+        match self.query[start..self.position].parse::<i64>() {
+            Ok(integer) if (-MAX_INTEGER..=MAX_INTEGER).contains(&integer) => Ok(integer),
+            _ => {
+                self.position = start;
+                Err(self.error("integer out of range"))
+            }
+        }
+    }
+
+    fn parse_hex_escape(&mut self) -> Result<u32, Error> {
+        let hex_digits = self
+            .query
+            .get(self.position..self.position + 4)
+            .filter(|hex_digits| hex_digits.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        if let Some(code_unit) = hex_digits.and_then(|hex_digits| u32::from_str_radix(hex_digits, 16).ok()) {
+            self.position += 4;
+            Ok(code_unit)
+        } else {
+            Err(self.error("expected 4 hexadecimal digits"))
+        }
+    }
+
+    fn parse_string_literal(&mut self) -> Result<String, Error> {
+        let quote = self.peek().ok_or_else(|| self.error("expected a string"))?;
+        self.position += 1;
+        let mut result = String::new();
+        loop {
+            let character = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+            if character == quote {
+                self.position += 1;
+                return Ok(result);
+            } else if character < ' ' {
+                return Err(self.error("control characters must be escaped"));
+            } else if character == '\\' {
+                let escape_start = self.position;
+                self.position += 1;
+                let escaped = self.peek();
+                self.position += escaped.map_or(0, char::len_utf8);
+                match escaped {
+                    Some('b') => result.push('\u{8}'),
+                    Some('f') => result.push('\u{c}'),
+                    Some('n') => result.push('\n'),
+                    Some('r') => result.push('\r'),
+                    Some('t') => result.push('\t'),
+                    Some('/') => result.push('/'),
+                    Some('\\') => result.push('\\'),
+                    Some(escaped) if escaped == quote => result.push(quote),
+                    Some('u') => {
+                        let mut code_point = self.parse_hex_escape()?;
+                        if (0xD800..0xDC00).contains(&code_point) && self.consume("\\u") {
+                            let low_surrogate = self.parse_hex_escape()?;
+                            if (0xDC00..0xE000).contains(&low_surrogate) {
+                                code_point = 0x10000 + ((code_point - 0xD800) << 10) + (low_surrogate - 0xDC00);
+                            }
+                        }
+                        if let Some(character) = std::char::from_u32(code_point) {
+                            result.push(character);
+                        } else {
+                            self.position = escape_start;
+                            return Err(self.error("unpaired surrogate"));
+                        }
+                    }
+                    _ => {
+                        self.position = escape_start;
+                        return Err(self.error("invalid escape sequence"));
+                    }
+                }
+            } else {
+                result.push(character);
+                self.position += character.len_utf8();
+            }
+        }
+    }
+
+    fn parse_number_literal(&mut self) -> Result<RustType, Error> {
+        let start = self.position;
+        let _ = self.consume("-");
+        let digits_start = self.position;
+        while let Some('0'..='9') = self.peek() {
+            self.position += 1;
+        }
+        let integer_digits = &self.query[digits_start..self.position];
+        if integer_digits.is_empty() || (integer_digits.len() > 1 && integer_digits.starts_with('0')) {
+            self.position = start;
+            return Err(self.error("invalid number"));
+        }
+        if self.consume(".") && !self.consume_digits() {
+            return Err(self.error("expected a digit after the decimal point"));
+        }
+        if self.consume("e") || self.consume("E") {
+            let _ = self.consume("+") || self.consume("-");
+            if !self.consume_digits() {
+                return Err(self.error("expected a digit in the exponent"));
+            }
+        }
+        // The number literal syntax is a subset of JSON number syntax
+        parse_json(&self.query[start..self.position]).map_err(|_| {
+            self.position = start;
+            self.error("number out of range")
+        })
+    }
+
+    fn consume_digits(&mut self) -> bool {
+        let start = self.position;
+        while let Some('0'..='9') = self.peek() {
+            self.position += 1;
+        }
+        self.position > start
+    }
+
+    fn parse_function_name(&mut self) -> &str {
+        let start = self.position;
+        while let Some(character) = self.peek() {
+            if character.is_ascii_lowercase() || (self.position > start && (character.is_ascii_digit() || character == '_')) {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        &self.query[start..self.position]
+    }
+
+    fn parse_function(&mut self, name: &str, name_position: usize) -> Result<Function, Error> {
+        self.expect("(")?;
+        self.skip_blanks();
+        let function = match name {
+            "length" => Function::Length(Box::new(self.parse_comparable()?)),
+            "count" | "value" => {
+                if !self.starts_with("@") && !self.starts_with("$") {
+                    return Err(self.error("expected a filter query"));
+                }
+                let query = self.parse_query()?;
+                if name == "count" {
+                    Function::Count(query)
+                } else {
+                    Function::Value(query)
+                }
+            }
+            _ => {
+                self.position = name_position;
+                return Err(self.error(&format!("unsupported function `{name}`")));
+            }
+        };
+        self.skip_blanks();
+        self.expect(")")?;
+        Ok(function)
+    }
+
+    // Parse a value that can be compared (literal, singular query or function returning a value)
+    fn parse_comparable(&mut self) -> Result<Comparable, Error> {
+        let start = self.position;
+        match self.peek() {
+            Some('@' | '$') => {
+                let query = self.parse_query()?;
+                if query.is_singular() {
+                    Ok(Comparable::SingularQuery(query))
+                } else {
+                    self.position = start;
+                    Err(self.error("only singular queries can be compared"))
+                }
+            }
+            Some('\'' | '"') => Ok(Comparable::Literal(RustType::String(self.parse_string_literal()?))),
+            Some('-' | '0'..='9') => Ok(Comparable::Literal(self.parse_number_literal()?)),
+            Some('a'..='z') => {
+                let name = self.parse_function_name().to_string();
+                if self.starts_with("(") {
+                    Ok(Comparable::Function(self.parse_function(&name, start)?))
+                } else {
+                    match name.as_str() {
+                        "true" => Ok(Comparable::Literal(RustType::Boolean(true))),
+                        "false" => Ok(Comparable::Literal(RustType::Boolean(false))),
+                        "null" => Ok(Comparable::Literal(RustType::Null)),
+                        _ => {
+                            self.position = start;
+                            Err(self.error("expected a value"))
+                        }
+                    }
+                }
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_comparison_operator(&mut self) -> Option<ComparisonOperator> {
+        for (token, operator) in &[
+            ("==", ComparisonOperator::Equal),
+            ("!=", ComparisonOperator::NotEqual),
+            ("<=", ComparisonOperator::LessOrEqual),
+            (">=", ComparisonOperator::GreaterOrEqual),
+            ("<", ComparisonOperator::Less),
+            (">", ComparisonOperator::Greater),
+        ] {
+            if self.consume(token) {
+                return Some(*operator);
+            }
+        }
+        None
+    }
+
+    fn parse_logical_or(&mut self) -> Result<LogicalExpression, Error> {
+        let mut operands = vec![self.parse_logical_and()?];
+        loop {
+            let position_before_blanks = self.position;
+            self.skip_blanks();
+            if self.consume("||") {
+                self.skip_blanks();
+                operands.push(self.parse_logical_and()?);
+            } else {
+                self.position = position_before_blanks;
+                break;
+            }
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { LogicalExpression::Or(operands) })
+    }
+
+    fn parse_logical_and(&mut self) -> Result<LogicalExpression, Error> {
+        let mut operands = vec![self.parse_basic_expression()?];
+        loop {
+            let position_before_blanks = self.position;
+            self.skip_blanks();
+            if self.consume("&&") {
+                self.skip_blanks();
+                operands.push(self.parse_basic_expression()?);
+            } else {
+                self.position = position_before_blanks;
+                break;
+            }
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { LogicalExpression::And(operands) })
+    }
+
+    fn parse_basic_expression(&mut self) -> Result<LogicalExpression, Error> {
+        if self.consume("!") {
+            self.skip_blanks();
+            let start = self.position;
+            let expression = if self.starts_with("(") {
+                self.parse_parenthesized_expression()?
+            } else if self.starts_with("@") || self.starts_with("$") {
+                LogicalExpression::Exists(self.parse_query()?)
+            } else {
+                return Err(self.error("expected `(` or a filter query"));
+            };
+            let position_before_blanks = self.position;
+            self.skip_blanks();
+            if self.parse_comparison_operator().is_some() {
+                self.position = start;
+                return Err(self.error("comparisons have to be enclosed in parenthesis to be negated"));
+            }
+            self.position = position_before_blanks;
+            return Ok(LogicalExpression::Not(Box::new(expression)));
+        }
+        if self.starts_with("(") {
+            return self.parse_parenthesized_expression();
+        }
+
+        let start = self.position;
+        let left = match self.peek() {
+            // Queries might be existence tests or part of comparisons (if they are singular)
+            Some('@' | '$') => {
+                let query = self.parse_query()?;
+                let position_before_blanks = self.position;
+                self.skip_blanks();
+                if !self.starts_with("==") && !self.starts_with("!=") && !self.starts_with("<") && !self.starts_with(">") {
+                    self.position = position_before_blanks;
+                    return Ok(LogicalExpression::Exists(query));
+                }
+                if !query.is_singular() {
+                    self.position = start;
+                    return Err(self.error("only singular queries can be compared"));
+                }
+                Comparable::SingularQuery(query)
+            }
+            _ => self.parse_comparable()?,
+        };
+        self.skip_blanks();
+        let operator = self.parse_comparison_operator().ok_or_else(|| self.error("expected a comparison operator"))?;
+        self.skip_blanks();
+        let right = self.parse_comparable()?;
+        Ok(LogicalExpression::Comparison(left, operator, right))
+    }
+
+    fn parse_parenthesized_expression(&mut self) -> Result<LogicalExpression, Error> {
+        self.expect("(")?;
+        self.skip_blanks();
+        let expression = self.parse_logical_or()?;
+        self.skip_blanks();
+        self.expect(")")?;
+        Ok(expression)
+    }
+}
+
+// Node of the queried document with its location
+struct Node<'json, T: JsonType> {
+    value: &'json T,
+    path: Vec<PathComponent>,
+}
+
+impl<'json, T: JsonType> Node<'json, T> {
+    fn child(&self, value: &'json T, path_component: PathComponent) -> Self {
+        let mut path = self.path.clone();
+        path.push(path_component);
+        Self { value, path }
+    }
+
+    fn children(&self) -> Result<Vec<Self>, Error> {
+        Ok(match self.value.try_primitive_type()? {
+            PrimitiveType::Array => self
+                .value
+                .as_array()
+                .map(|items| items.enumerate().map(|(index, item)| self.child(item, PathComponent::Index(index))).collect())
+                .unwrap_or_default(),
+            PrimitiveType::Object => self
+                .value
+                .as_object()
+                .map(|object| object.keys().map(ToString::to_string).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|key| self.value.get_attribute(&key).map(|value| self.child(value, PathComponent::Attribute(key))))
+                .collect(),
+            _ => Vec::new(),
+        })
+    }
+}
+
+// Value of a comparable expression, Nothing represents the absence of a value (ie. singular query not matching any node)
+enum ComparableValue<'json, T: JsonType> {
+    Node(&'json T),
+    Owned(RustType),
+    Nothing,
+}
+
+fn is_equal<A: JsonType, B: JsonType>(a: &A, b: &B) -> bool {
+    json_eq(a, b)
+}
+
+// Only numbers and strings can be ordered
+fn is_less<A: JsonType, B: JsonType>(a: &A, b: &B) -> Result<bool, Error> {
+    let is_numeric = |primitive_type| primitive_type == PrimitiveType::Integer || primitive_type == PrimitiveType::Number;
+    Ok(if is_numeric(a.try_primitive_type()?) && is_numeric(b.try_primitive_type()?) {
+        json_cmp(a, b) == Ordering::Less
+    } else if let (Some(a_string), Some(b_string)) = (a.as_string(), b.as_string()) {
+        a_string < b_string
+    } else {
+        false
+    })
+}
+
+struct Evaluator<'json, T: JsonType> {
+    root: &'json T,
+}
+
+impl<'json, T: JsonType> Evaluator<'json, T> {
+    fn evaluate_query(&self, query: &Query, current: &'json T) -> Result<Vec<Node<'json, T>>, Error> {
+        let start = Node {
+            value: if query.is_relative { current } else { self.root },
+            path: Vec::new(),
+        };
+        let mut nodes = vec![start];
+        for segment in &query.segments {
+            nodes = match segment {
+                Segment::Child(selectors) => self.select_all(selectors, &nodes)?,
+                Segment::Descendant(selectors) => {
+                    let mut descendants = Vec::new();
+                    for node in nodes {
+                        Self::collect_descendants(node, &mut descendants)?;
+                    }
+                    self.select_all(selectors, &descendants)?
+                }
+            };
+        }
+        Ok(nodes)
+    }
+
+    // Collect `node` and all its descendants in document order
+    fn collect_descendants(node: Node<'json, T>, descendants: &mut Vec<Node<'json, T>>) -> Result<(), Error> {
+        let children = node.children()?;
+        descendants.push(node);
+        for child in children {
+            Self::collect_descendants(child, descendants)?;
+        }
+        Ok(())
+    }
+
+    fn select_all(&self, selectors: &[Selector], nodes: &[Node<'json, T>]) -> Result<Vec<Node<'json, T>>, Error> {
+        let mut result = Vec::new();
+        for node in nodes {
+            result.extend(self.select(selectors, node)?);
+        }
+        Ok(result)
+    }
+
+    fn select(&self, selectors: &[Selector], node: &Node<'json, T>) -> Result<Vec<Node<'json, T>>, Error> {
+        let mut result = Vec::new();
+        for selector in selectors {
+            match selector {
+                Selector::Name(name) => {
+                    if let Some(value) = node.value.get_attribute(name) {
+                        result.push(node.child(value, PathComponent::Attribute(name.clone())));
+                    }
+                }
+                Selector::Wildcard => result.extend(node.children()?),
+                Selector::Index(index) => {
+                    if let Some(length) = node.value.as_array().map(|items| items.len()) {
+                        if let Some(index) = normalize_index(*index, length) {
+                            if let Some(value) = node.value.get_index(index) {
+                                result.push(node.child(value, PathComponent::Index(index)));
+                            }
+                        }
+                    }
+                }
+                Selector::Slice { start, end, step } => {
+                    if let Some(length) = node.value.as_array().map(|items| items.len()) {
+                        for index in slice_indexes(*start, *end, *step, length) {
+                            if let Some(value) = node.value.get_index(index) {
+                                result.push(node.child(value, PathComponent::Index(index)));
+                            }
+                        }
+                    }
+                }
+                Selector::Filter(expression) => {
+                    for child in node.children()? {
+                        if self.evaluate_logical_expression(expression, child.value)? {
+                            result.push(child);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn evaluate_logical_expression(&self, expression: &LogicalExpression, current: &'json T) -> Result<bool, Error> {
+        Ok(match expression {
+            LogicalExpression::Or(operands) => {
+                for operand in operands {
+                    if self.evaluate_logical_expression(operand, current)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            LogicalExpression::And(operands) => {
+                for operand in operands {
+                    if !self.evaluate_logical_expression(operand, current)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            LogicalExpression::Not(operand) => !self.evaluate_logical_expression(operand, current)?,
+            LogicalExpression::Exists(query) => !self.evaluate_query(query, current)?.is_empty(),
+            LogicalExpression::Comparison(left, operator, right) => {
+                let left = self.evaluate_comparable(left, current)?;
+                let right = self.evaluate_comparable(right, current)?;
+                match operator {
+                    ComparisonOperator::Equal => compare_equal(&left, &right),
+                    ComparisonOperator::NotEqual => !compare_equal(&left, &right),
+                    ComparisonOperator::Less => compare_less(&left, &right)?,
+                    ComparisonOperator::LessOrEqual => compare_less(&left, &right)? || compare_equal(&left, &right),
+                    ComparisonOperator::Greater => compare_less(&right, &left)?,
+                    ComparisonOperator::GreaterOrEqual => compare_less(&right, &left)? || compare_equal(&left, &right),
+                }
+            }
+        })
+    }
+
+    fn evaluate_comparable(&self, comparable: &Comparable, current: &'json T) -> Result<ComparableValue<'json, T>, Error> {
+        Ok(match comparable {
+            Comparable::Literal(literal) => ComparableValue::Owned(literal.clone()),
+            Comparable::SingularQuery(query) => self
+                .evaluate_query(query, current)?
+                .first()
+                .map_or(ComparableValue::Nothing, |node| ComparableValue::Node(node.value)),
+            Comparable::Function(Function::Length(argument)) => {
+                let length = match self.evaluate_comparable(argument, current)? {
+                    ComparableValue::Node(value) => json_length(value)?,
+                    ComparableValue::Owned(value) => json_length(&value)?,
+                    ComparableValue::Nothing => None,
+                };
+                length.map_or(ComparableValue::Nothing, |length| ComparableValue::Owned(RustType::Integer(length as i128)))
+            }
+            Comparable::Function(Function::Count(query)) => ComparableValue::Owned(RustType::Integer(self.evaluate_query(query, current)?.len() as i128)),
+            Comparable::Function(Function::Value(query)) => {
+                let nodes = self.evaluate_query(query, current)?;
+                if nodes.len() == 1 {
+                    ComparableValue::Node(nodes[0].value)
+                } else {
+                    ComparableValue::Nothing
+                }
+            }
+        })
+    }
+}
+
+// Implementation of the `length` function extension
+fn json_length<T: JsonType>(value: &T) -> Result<Option<usize>, Error> {
+    Ok(match value.try_primitive_type()? {
+        PrimitiveType::String => value.as_string().map(|string| string.chars().count()),
+        PrimitiveType::Array => value.as_array().map(|items| items.len()),
+        PrimitiveType::Object => value.as_object().map(|object| object.keys().count()),
+        _ => None,
+    })
+}
+
+fn compare_equal<T: JsonType>(left: &ComparableValue<T>, right: &ComparableValue<T>) -> bool {
+    match (left, right) {
+        (ComparableValue::Nothing, ComparableValue::Nothing) => true,
+        (ComparableValue::Node(left), ComparableValue::Node(right)) => is_equal(*left, *right),
+        (ComparableValue::Node(left), ComparableValue::Owned(right)) => is_equal(*left, right),
+        (ComparableValue::Owned(left), ComparableValue::Node(right)) => is_equal(left, *right),
+        (ComparableValue::Owned(left), ComparableValue::Owned(right)) => is_equal(left, right),
+        _ => false,
+    }
+}
+
+fn compare_less<T: JsonType>(left: &ComparableValue<T>, right: &ComparableValue<T>) -> Result<bool, Error> {
+    match (left, right) {
+        (ComparableValue::Node(left), ComparableValue::Node(right)) => is_less(*left, *right),
+        (ComparableValue::Node(left), ComparableValue::Owned(right)) => is_less(*left, right),
+        (ComparableValue::Owned(left), ComparableValue::Node(right)) => is_less(left, *right),
+        (ComparableValue::Owned(left), ComparableValue::Owned(right)) => is_less(left, right),
+        _ => Ok(false),
+    }
+}
+
+fn normalize_index(index: i64, length: usize) -> Option<usize> {
+    let length = i64::try_from(length).ok()?;
+    let index = if index < 0 { length + index } else { index };
+    if 0 <= index && index < length {
+        usize::try_from(index).ok()
+    } else {
+        None
+    }
+}
+
+// Indexes selected by a slice selector according to RFC 9535 Section 2.3.4.2.2
+fn slice_indexes(start: Option<i64>, end: Option<i64>, step: Option<i64>, length: usize) -> Vec<usize> {
+    let length = i64::try_from(length).unwrap_or(i64::MAX);
+    let step = step.unwrap_or(1);
+    let normalize = |index: i64| if index >= 0 { index } else { length + index };
+    let mut indexes = Vec::new();
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).max(0).min(length);
+        let upper = normalize(end.unwrap_or(length)).max(0).min(length);
+        let mut index = lower;
+        while index < upper {
+            indexes.extend(usize::try_from(index).ok());
+            index += step;
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(length - 1)).max(-1).min(length - 1);
+        let lower = end.map_or(-1, |end| normalize(end).max(-1).min(length - 1));
+        let mut index = upper;
+        while lower < index {
+            indexes.extend(usize::try_from(index).ok());
+            index += step;
+        }
+    }
+    indexes
+}
+
+impl JsonPath {
+    /// Parse a `JSONPath` query (ie. `$.store.book[?@.price < 10].title`)
+    ///
+    /// # Errors
+    /// `Error::InvalidJsonPath` is returned if `query` is not a valid (or supported) `JSONPath` query
+    pub fn parse(query: &str) -> Result<Self, Error> {
+        let mut parser = QueryParser { query, position: 0 };
+        if !parser.starts_with("$") {
+            return Err(parser.error("expected `$`"));
+        }
+        let parsed_query = parser.parse_query()?;
+        if parser.position == query.len() {
+            Ok(Self { query: parsed_query })
+        } else {
+            Err(parser.error("unexpected character"))
+        }
+    }
+
+    /// Evaluate the query on `value`, the matched nodes are returned in the order defined by RFC 9535
+    ///
+    /// # Errors
+    /// `Error::UnrecognizedValue` is returned if an inspected node of `value` is not a recognized JSON value
+    pub fn query<'json, T: JsonType>(&self, value: &'json T) -> Result<Vec<JsonPathMatch<'json, T>>, Error> {
+        Ok(Evaluator { root: value }
+            .evaluate_query(&self.query, value)?
+            .into_iter()
+            .map(|node| JsonPathMatch {
+                value: node.value,
+                path: node.path,
+            })
+            .collect())
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = Error;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        Self::parse(query)
+    }
+}
+
+/// Evaluate the `JSONPath` `query` on `value`
+///
+/// # Errors
+/// `Error::InvalidJsonPath` is returned if `query` is not a valid (or supported) `JSONPath` query,
+/// `Error::UnrecognizedValue` is returned if an inspected node of `value` is not a recognized JSON value
+pub fn json_path_query<'json, T: JsonType>(value: &'json T, query: &str) -> Result<Vec<JsonPathMatch<'json, T>>, Error> {
+    JsonPath::parse(query)?.query(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_path_query, JsonPath, JsonPathMatch};
+    use crate::{error::Error, json_type::JsonType, rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use test_case::test_case;
+
+    lazy_static! {
+        // Example from RFC 9535 Section 1.5
+        static ref STORE: RustType = rust_type!({
+            "store": {
+                "book": [
+                    {"category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95},
+                    {"category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99},
+                    {"category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99},
+                    {"category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99}
+                ],
+                "bicycle": {"color": "red", "price": 399}
+            }
+        });
+    }
+
+    fn normalized_paths(value: &RustType, query: &str) -> Vec<String> {
+        json_path_query(value, query).unwrap().iter().map(JsonPathMatch::normalized_path).collect()
+    }
+
+    #[test_case("$.store.book[*].author" => vec!["$['store']['book'][0]['author']", "$['store']['book'][1]['author']", "$['store']['book'][2]['author']", "$['store']['book'][3]['author']"] ; "authors of all books")]
+    #[test_case("$..author" => vec!["$['store']['book'][0]['author']", "$['store']['book'][1]['author']", "$['store']['book'][2]['author']", "$['store']['book'][3]['author']"] ; "all authors")]
+    #[test_case("$.store.*" => vec!["$['store']['book']", "$['store']['bicycle']"] ; "all things in store")]
+    #[test_case("$.store..price" => vec!["$['store']['book'][0]['price']", "$['store']['book'][1]['price']", "$['store']['book'][2]['price']", "$['store']['book'][3]['price']", "$['store']['bicycle']['price']"] ; "prices of everything in the store")]
+    #[test_case("$..book[2]" => vec!["$['store']['book'][2]"] ; "third book")]
+    #[test_case("$..book[-1]" => vec!["$['store']['book'][3]"] ; "last book")]
+    #[test_case("$..book[0,1]" => vec!["$['store']['book'][0]", "$['store']['book'][1]"] ; "first two books with union")]
+    #[test_case("$..book[:2]" => vec!["$['store']['book'][0]", "$['store']['book'][1]"] ; "first two books with slice")]
+    #[test_case("$..book[?@.isbn]" => vec!["$['store']['book'][2]", "$['store']['book'][3]"] ; "books with isbn")]
+    #[test_case("$..book[?@.price<10]" => vec!["$['store']['book'][0]", "$['store']['book'][2]"] ; "books cheaper than 10")]
+    #[test_case("$..book[?@.price < $.store.bicycle.price && @.category == 'fiction' && !(@.author == 'Evelyn Waugh')]" => vec!["$['store']['book'][2]", "$['store']['book'][3]"] ; "logical operators")]
+    #[test_case("$..book[?@.category == \"reference\" || @.price > 20]" => vec!["$['store']['book'][0]", "$['store']['book'][3]"] ; "logical or")]
+    #[test_case("$.store['bicycle', 'book'][?@ == 'red']" => vec!["$['store']['bicycle']['color']"] ; "filter on object")]
+    #[test_case("$.store[?length(@) == 4]" => vec!["$['store']['book']"] ; "length function")]
+    #[test_case("$.store.book[?count(@.*) == 5]" => vec!["$['store']['book'][2]", "$['store']['book'][3]"] ; "count function")]
+    #[test_case("$.store.book[?value(@..isbn) == '0-553-21311-3']" => vec!["$['store']['book'][2]"] ; "value function")]
+    #[test_case("$.store.book[?@.missing == @.other]" => vec!["$['store']['book'][0]", "$['store']['book'][1]", "$['store']['book'][2]", "$['store']['book'][3]"] ; "nothing equals nothing")]
+    #[test_case("$.store.bicycle[?@ > 'a']" => vec!["$['store']['bicycle']['color']"] ; "strings comparison")]
+    #[test_case("$..*[?@ == 399.0]" => vec!["$['store']['bicycle']['price']"] ; "integers and numbers comparison")]
+    #[test_case("$.store.missing" => Vec::<String>::new() ; "missing attribute")]
+    fn test_rfc_examples(query: &str) -> Vec<String> {
+        normalized_paths(&STORE, query)
+    }
+
+    #[test_case("$[1:3]" => vec![1, 2] ; "slice")]
+    #[test_case("$[5:]" => vec![5, 6] ; "slice without end")]
+    #[test_case("$[1:5:2]" => vec![1, 3] ; "slice with step")]
+    #[test_case("$[5:1:-2]" => vec![5, 3] ; "slice with negative step")]
+    #[test_case("$[::-1]" => vec![6, 5, 4, 3, 2, 1, 0] ; "reverse slice")]
+    #[test_case("$[-2:]" => vec![5, 6] ; "slice with negative start")]
+    #[test_case("$[1:3:0]" => Vec::<i128>::new() ; "slice with zero step")]
+    #[test_case("$[-1, 0, 7, -8]" => vec![6, 0] ; "indexes")]
+    #[test_case("$[?@ >= 5]" => vec![5, 6] ; "filter on integers")]
+    #[test_case("$[?@ < 2 || @ == 6]" => vec![0, 1, 6] ; "logical or")]
+    fn test_array_selectors(query: &str) -> Vec<i128> {
+        let value = rust_type!([0, 1, 2, 3, 4, 5, 6]);
+        json_path_query(&value, query)
+            .unwrap()
+            .iter()
+            .filter_map(|json_path_match| json_path_match.value.as_integer())
+            .collect()
+    }
+
+    #[test]
+    fn test_normalized_path_escaping() {
+        let value = rust_type!({"a'b\\\u{1}\n": [{"": true}]});
+        let matches = json_path_query(&value, "$.*[0]['']").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].normalized_path(), "$['a\\'b\\\\\\u0001\\n'][0]['']");
+        assert_eq!(matches[0].fragment(), "/a'b\\\u{1}\n/0/");
+    }
+
+    #[test_case("$[0]" => Ok(1) ; "unrecognized value not inspected")]
+    #[test_case("$..*" => Err(Error::UnrecognizedValue) ; "descendant segment")]
+    #[test_case("$[?@ < 2]" => Err(Error::UnrecognizedValue) ; "comparison")]
+    #[test_case("$[?length(@) == 1]" => Err(Error::UnrecognizedValue) ; "length function")]
+    fn test_query_unrecognized_value(query: &str) -> Result<usize, Error> {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        json_path_query(&value, query).map(|matches| matches.len())
+    }
+
+    #[test]
+    fn test_descendant_order() {
+        let value = rust_type!({"a": [{"a": 1}, {"b": {"a": 2}}]});
+        let values = json_path_query(&value, "$..a")
+            .unwrap()
+            .iter()
+            .map(|json_path_match| json_path_match.value.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![rust_type!([{"a": 1}, {"b": {"a": 2}}]), rust_type!(1), rust_type!(2)]);
+    }
+
+    #[test_case("" => Error::InvalidJsonPath { position: 0, reason: "expected `$`".to_string() } ; "empty query")]
+    #[test_case("$ " => Error::InvalidJsonPath { position: 1, reason: "unexpected character".to_string() } ; "trailing blank")]
+    #[test_case("$.1" => Error::InvalidJsonPath { position: 2, reason: "expected a member name".to_string() } ; "invalid member name")]
+    #[test_case("$[01]" => Error::InvalidJsonPath { position: 2, reason: "integers cannot have leading zeros".to_string() } ; "leading zero")]
+    #[test_case("$[-0]" => Error::InvalidJsonPath { position: 2, reason: "integers cannot have leading zeros".to_string() } ; "negative zero")]
+    #[test_case("$[9007199254740992]" => Error::InvalidJsonPath { position: 2, reason: "integer out of range".to_string() } ; "integer out of range")]
+    #[test_case("$['a'" => Error::InvalidJsonPath { position: 5, reason: "expected `,`".to_string() } ; "unterminated bracket")]
+    #[test_case("$['\\x']" => Error::InvalidJsonPath { position: 3, reason: "invalid escape sequence".to_string() } ; "invalid escape")]
+    #[test_case("$[?@.* == 1]" => Error::InvalidJsonPath { position: 3, reason: "only singular queries can be compared".to_string() } ; "non singular comparison")]
+    #[test_case("$[?!@.a == 1]" => Error::InvalidJsonPath { position: 4, reason: "comparisons have to be enclosed in parenthesis to be negated".to_string() } ; "negated comparison")]
+    #[test_case("$[?match(@, 'a')]" => Error::InvalidJsonPath { position: 3, reason: "unsupported function `match`".to_string() } ; "unsupported function")]
+    #[test_case("$[?1]" => Error::InvalidJsonPath { position: 4, reason: "expected a comparison operator".to_string() } ; "literal as test expression")]
+    fn test_invalid_queries(query: &str) -> Error {
+        JsonPath::parse(query).unwrap_err()
+    }
+
+    #[test]
+    fn test_json_path_is_reusable() {
+        let json_path = "$..price".parse::<JsonPath>().unwrap();
+        assert_eq!(json_path.query(&*STORE).unwrap().len(), 5);
+        assert_eq!(json_path.query(&rust_type!({"price": 1})).unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_json_path_on_serde_json() {
+        let value = json!({"users": [{"name": "a", "age": 17}, {"name": "b", "age": 42}]});
+        let matches = json_path_query(&value, "$.users[?@.age >= 18].name").unwrap();
+        assert_eq!(matches.iter().map(|json_path_match| json_path_match.value).collect::<Vec<_>>(), vec![&json!("b")]);
+        assert_eq!(matches[0].fragment(), "/users/1/name");
+    }
+}
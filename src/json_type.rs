@@ -1,5 +1,11 @@
-use crate::{error::Error, fragment_helpers::fragment_components_from_fragment, rust_type_impl::RustType};
-use std::{collections::HashMap, convert::TryFrom, fmt::Debug, ops::Deref};
+use crate::{
+    error::{Error, PointerError, PointerErrorReason},
+    fragment_helpers::{array_index_from_fragment_part, fragment_components_from_fragment, fragment_from_fragment_components},
+    rust_type_impl::RustType,
+    serializer::{to_canonical_json_string, to_json_string_lossy},
+};
+use indexmap::IndexMap;
+use std::{convert::TryFrom, fmt::Debug, iter::once, ops::Deref};
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Copy, EnumIter, EnumVariantNames, Eq, Hash, Debug, Display, PartialEq)]
@@ -57,31 +63,45 @@ pub trait JsonMapTrait<'json, T: 'json + JsonType> {
 }
 
 pub trait ToRustType {
+    /// # Panics
+    /// The method panics if the value, or any of its descendants, is not recognized as a JSON primitive type.
+    /// Use `try_to_rust_type` if the backend might hold such values (ie. Python objects).
     fn to_rust_type(&self) -> RustType
     where
         Self: Sized + JsonType,
     {
-        if let Some(array) = self.as_array() {
-            RustType::from(array.map(|item| item.to_rust_type()).collect::<Vec<_>>())
-        } else if let Some(bool) = self.as_boolean() {
-            RustType::from(bool)
-        } else if let Some(integer) = self.as_integer() {
-            RustType::from(integer)
-        } else if self.is_null() {
-            RustType::from(())
-        } else if let Some(number) = self.as_number() {
-            RustType::from(number)
-        } else if let Some(object) = self.as_object() {
-            RustType::from(object.items().map(|(k, v)| (k.into(), v.to_rust_type())).collect::<HashMap<_, _>>())
-        } else if let Some(string) = self.as_string() {
-            RustType::from(string)
-        } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+        match self.try_to_rust_type() {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
         }
     }
+
+    /// # Errors
+    /// `Error::UnrecognizedValue` is returned if the value, or any of its descendants, is not recognized as a JSON primitive type
+    fn try_to_rust_type(&self) -> Result<RustType, Error>
+    where
+        Self: Sized + JsonType,
+    {
+        Ok(match self.try_primitive_type()? {
+            PrimitiveType::Array => RustType::from(self.as_array().into_iter().flatten().map(ToRustType::try_to_rust_type).collect::<Result<Vec<_>, _>>()?),
+            PrimitiveType::Boolean => RustType::from(self.as_boolean().unwrap_or_default()),
+            PrimitiveType::Integer => RustType::from(self.as_integer().unwrap_or_default()),
+            PrimitiveType::Null => RustType::from(()),
+            PrimitiveType::Number => RustType::from(self.as_number().unwrap_or_default()),
+            PrimitiveType::Object => RustType::from(
+                self.as_object()
+                    .map(|object| {
+                        object
+                            .items()
+                            .map(|(key, value)| Ok((key.into(), value.try_to_rust_type()?)))
+                            .collect::<Result<IndexMap<_, _>, Error>>()
+                    })
+                    .transpose()?
+                    .unwrap_or_default(),
+            ),
+            PrimitiveType::String => RustType::from(self.as_string().unwrap_or_default()),
+        })
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -89,6 +109,12 @@ pub trait JsonTypeToString {
     fn to_json_string(&self) -> String;
 }
 
+// Canonical representation (RFC 8785) of JSON-like objects, the representation does not depend on the JsonType implementation
+#[allow(clippy::module_name_repetitions, clippy::missing_errors_doc)]
+pub trait JsonTypeToCanonicalString {
+    fn to_canonical_json_string(&self) -> Result<String, Error>;
+}
+
 // This trait allows us to have a 1:1 mapping with serde_json, generally used by rust libraries
 // but gives us the power to use different objects from serde_json. This gives us the ability
 // to support usage of different data-types like PyObject from pyo3 in case of python bindings
@@ -153,37 +179,218 @@ pub trait JsonType: JsonTypeToString + ToRustType {
         self.get_attribute(attribute_name).is_some()
     }
 
+    /// # Panics
+    /// The method panics if the value is not recognized as a JSON primitive type, see `try_primitive_type`
     fn primitive_type(&self) -> PrimitiveType
+    where
+        Self: Sized,
+    {
+        match self.try_primitive_type() {
+            Ok(primitive_type) => primitive_type,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// # Errors
+    /// `Error::UnrecognizedValue` is returned if none of the `as_*` accessors recognizes the value
+    fn try_primitive_type(&self) -> Result<PrimitiveType, Error>
     where
         Self: Sized,
     {
         // This might not be efficient, but it could be comfortable to quickly extract the type especially while debugging
         if self.is_array() {
-            PrimitiveType::Array
+            Ok(PrimitiveType::Array)
         } else if self.is_boolean() {
-            PrimitiveType::Boolean
+            Ok(PrimitiveType::Boolean)
         } else if self.is_integer() {
-            PrimitiveType::Integer
+            Ok(PrimitiveType::Integer)
         } else if self.is_null() {
-            PrimitiveType::Null
+            Ok(PrimitiveType::Null)
         } else if self.is_number() {
-            PrimitiveType::Number
+            Ok(PrimitiveType::Number)
         } else if self.is_object() {
-            PrimitiveType::Object
+            Ok(PrimitiveType::Object)
         } else if self.is_string() {
-            PrimitiveType::String
+            Ok(PrimitiveType::String)
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            Err(Error::UnrecognizedValue)
+        }
+    }
+
+    // Fallible counterparts of the accessors, they report `Error::UnexpectedPrimitiveType` if the value is of a different type
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_array<'json>(&'json self) -> Result<Box<dyn ExactSizeIterator<Item = &'json Self> + 'json>, Error>
+    where
+        Self: Sized,
+    {
+        self.as_array().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::Array))
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_boolean(&self) -> Result<bool, Error>
+    where
+        Self: Sized,
+    {
+        self.as_boolean().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::Boolean))
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_integer(&self) -> Result<i128, Error>
+    where
+        Self: Sized,
+    {
+        self.as_integer().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::Integer))
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_null(&self) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        self.as_null().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::Null))
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_number(&self) -> Result<f64, Error>
+    where
+        Self: Sized,
+    {
+        self.as_number().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::Number))
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_object(&self) -> Result<JsonMap<'_, Self>, Error>
+    where
+        Self: Sized,
+    {
+        self.as_object().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::Object))
+    }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn try_as_string(&self) -> Result<&str, Error>
+    where
+        Self: Sized,
+    {
+        self.as_string().ok_or_else(|| unexpected_primitive_type(self, PrimitiveType::String))
+    }
+
+    /// # Errors
+    /// `Error::UnexpectedPrimitiveType` is returned if the value is not an object and
+    /// `Error::InvalidPointer` if the attribute is not present
+    fn try_get_attribute(&self, attribute_name: &str) -> Result<&Self, Error>
+    where
+        Self: Sized,
+    {
+        if self.is_object() {
+            resolve_fragment(self, &fragment_from_fragment_components(once(attribute_name)))
+        } else {
+            Err(unexpected_primitive_type(self, PrimitiveType::Object))
         }
     }
+
+    /// # Errors
+    /// `Error::UnexpectedPrimitiveType` is returned if the value is not an array and
+    /// `Error::IndexOutOfBounds` if the array has no item at `index`
+    fn try_get_index(&self, index: usize) -> Result<&Self, Error>
+    where
+        Self: Sized,
+    {
+        let length = self.try_as_array()?.len();
+        self.get_index(index).ok_or(Error::IndexOutOfBounds { index, length })
+    }
+}
+
+// Used by the backends overriding the `try_*` accessors, `pub(crate)` as `unreachable_pub` is denied
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn unexpected_primitive_type<T: JsonType>(value: &T, expected: PrimitiveType) -> Error {
+    match value.try_primitive_type() {
+        Ok(actual) => Error::UnexpectedPrimitiveType { expected, actual },
+        Err(error) => error,
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
 pub trait ThreadSafeJsonType: JsonType + Sync + Send {}
 
+/// Mutable counterpart of `JsonType`. It allows in-place edits of JSON-like objects without having to
+/// know the concrete type backing them.
+///
+/// Mutating methods report `Error::UnexpectedPrimitiveType` if invoked on an instance of the wrong primitive type
+/// (ie. `set_attribute` on an array) and `Error::IndexOutOfBounds` if the requested index is not available.
+#[allow(clippy::module_name_repetitions, clippy::missing_errors_doc)]
+pub trait JsonTypeMut: JsonType {
+    fn get_attribute_mut(&mut self, attribute_name: &str) -> Option<&mut Self>
+    where
+        Self: Sized;
+    fn get_index_mut(&mut self, index: usize) -> Option<&mut Self>
+    where
+        Self: Sized;
+
+    // Set `attribute_name` to `value` and return the previously associated value, if any
+    fn set_attribute(&mut self, attribute_name: &str, value: Self) -> Result<Option<Self>, Error>
+    where
+        Self: Sized;
+    // Remove `attribute_name` and return the associated value, if any
+    fn remove_attribute(&mut self, attribute_name: &str) -> Result<Option<Self>, Error>
+    where
+        Self: Sized;
+
+    fn push(&mut self, value: Self) -> Result<(), Error>
+    where
+        Self: Sized;
+    // Insert `value` at position `index` shifting all the following elements (`index` equal to the array length is allowed)
+    fn insert_index(&mut self, index: usize, value: Self) -> Result<(), Error>
+    where
+        Self: Sized;
+    fn remove_index(&mut self, index: usize) -> Result<Self, Error>
+    where
+        Self: Sized;
+
+    // Replace the whole instance with `value` and return the previous one
+    #[must_use]
+    fn replace(&mut self, value: Self) -> Self
+    where
+        Self: Sized,
+    {
+        std::mem::replace(self, value)
+    }
+}
+
+/// Constructors of `JsonType` instances. It allows generic code to produce values of any backend
+/// (ie. via `convert::convert`) instead of being limited to `RustType`.
+///
+/// Backends that are not able to faithfully represent a value (ie. `serde_json` and integers outside of the i64/u64 range)
+/// fallback to the closest representation available.
+#[allow(clippy::module_name_repetitions)]
+pub trait JsonTypeBuilder: JsonType + Sized {
+    #[must_use]
+    fn null() -> Self;
+    #[must_use]
+    fn from_bool(value: bool) -> Self;
+    #[must_use]
+    fn from_i128(value: i128) -> Self;
+    #[must_use]
+    fn from_f64(value: f64) -> Self;
+    // Exact representation of `value`, None if the backend would need to fallback to an approximated representation
+    #[must_use]
+    fn try_from_i128(value: i128) -> Option<Self> {
+        Some(Self::from_i128(value))
+    }
+    // Exact representation of `value`, None if the backend is not able to represent it (ie. non finite numbers)
+    #[must_use]
+    fn try_from_f64(value: f64) -> Option<Self> {
+        Some(Self::from_f64(value))
+    }
+    // Named `from_string` to avoid ambiguities with `std::str::FromStr::from_str`
+    #[must_use]
+    fn from_string(value: &str) -> Self;
+    #[must_use]
+    fn from_array<I: IntoIterator<Item = Self>>(items: I) -> Self;
+    // If the same key is present multiple times the last value wins
+    #[must_use]
+    fn from_object<I: IntoIterator<Item = (String, Self)>>(items: I) -> Self;
+}
+
 #[derive(Debug)]
 pub struct JsonMap<'json, T: JsonType>(&'json T);
 
@@ -213,26 +420,75 @@ impl<'json, T: JsonType> JsonMapTrait<'json, T> for JsonMap<'json, T> {
         Box::new(self.items().map(|(_, value)| value))
     }
 
+    // The library relies on specialization to reduce the amount of trait constraints needed for JsonType, that's why this
+    // default implementation exists. Types without a specialized JsonMapTrait::items are treated as objects without attributes.
+    // NOTE: All the types implementing JsonType trait should take care of implementing at least JsonMapTrait::items as well.
     #[must_use]
-    default fn items(&'json self) -> Box<dyn Iterator<Item = (&str, &T)>> {
-        todo!("The library relies on specialization to reduce the amount of trait constraints needed for JsonType. That's why we have this dummy JsonMapTrait::items implementation. NOTE: All the types implementing JsonType trait should take care of implementing at least JsonMapTrait::items as well.")
+    default fn items(&'json self) -> Box<dyn Iterator<Item = (&str, &T)> + 'json> {
+        Box::new(std::iter::empty())
     }
 }
 
 impl<T: JsonType> JsonTypeToString for T {
     default fn to_json_string(&self) -> String {
-        self.to_rust_type().to_json_string()
+        to_json_string_lossy(self)
+    }
+}
+
+impl<T: JsonType> JsonTypeToCanonicalString for T {
+    fn to_canonical_json_string(&self) -> Result<String, Error> {
+        to_canonical_json_string(self)
     }
 }
 
 #[allow(clippy::module_name_repetitions)]
 pub fn get_fragment<'json, T: JsonType>(json_object: &'json T, fragment: &str) -> Option<&'json T> {
+    resolve_fragment(json_object, fragment).ok()
+}
+
+/// Resolve `fragment` (JSON Pointer as defined by RFC 6901) on `json_object`
+///
+/// # Errors
+/// `Error::InvalidPointer` is returned if `fragment` does not reference any value. The `PointerError` contains the failing
+/// component and the value that was resolved before it.
+/// `Error::UnrecognizedValue` is returned if a value traversed by `fragment` is not recognized as a JSON primitive type.
+#[allow(clippy::module_name_repetitions)]
+pub fn resolve_fragment<'json, T: JsonType>(json_object: &'json T, fragment: &str) -> Result<&'json T, Error> {
+    let fragment_components = fragment_components_from_fragment(fragment).collect::<Vec<_>>();
+    let mut result = json_object;
+    for (component_index, fragment_part) in fragment_components.iter().enumerate() {
+        let primitive_type = result.try_primitive_type()?;
+        let resolved = match primitive_type {
+            PrimitiveType::Object => result.get_attribute(fragment_part.as_str()).ok_or(PointerErrorReason::MissingAttribute),
+            PrimitiveType::Array => array_index_from_fragment_part(fragment_part)
+                .ok_or(PointerErrorReason::InvalidArrayIndex)
+                .and_then(|index| {
+                    result.get_index(index).ok_or_else(|| PointerErrorReason::IndexOutOfBounds {
+                        length: result.as_array().map_or(0, |items| items.len()),
+                    })
+                }),
+            _ => Err(PointerErrorReason::NotAContainer),
+        };
+        result = resolved.map_err(|reason| PointerError {
+            fragment: fragment.to_string(),
+            component_index,
+            component: fragment_part.clone(),
+            resolved_fragment: fragment_from_fragment_components(&fragment_components[..component_index]),
+            primitive_type,
+            reason,
+        })?;
+    }
+    Ok(result)
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub fn get_fragment_mut<'json, T: JsonTypeMut>(json_object: &'json mut T, fragment: &str) -> Option<&'json mut T> {
     let mut result = Some(json_object);
     for fragment_part in fragment_components_from_fragment(fragment) {
         if let Some(value) = result {
-            result = match value.primitive_type() {
-                PrimitiveType::Object => value.get_attribute(fragment_part.as_str()),
-                PrimitiveType::Array => fragment_part.parse::<usize>().map(|index| value.get_index(index)).ok().unwrap_or(None),
+            result = match value.try_primitive_type() {
+                Ok(PrimitiveType::Object) => value.get_attribute_mut(fragment_part.as_str()),
+                Ok(PrimitiveType::Array) => array_index_from_fragment_part(&fragment_part).and_then(move |index| value.get_index_mut(index)),
                 _ => None,
             };
         }
@@ -242,11 +498,79 @@ pub fn get_fragment<'json, T: JsonType>(json_object: &'json T, fragment: &str) -
 
 #[cfg(test)]
 mod tests {
-    use super::{get_fragment, Error, JsonType, PrimitiveType};
-    use crate::rust_type_impl::RustType;
+    use super::{get_fragment, get_fragment_mut, resolve_fragment, Error, JsonMap, JsonMapTrait, JsonType, PrimitiveType, ToRustType};
+    use crate::{
+        error::{PointerError, PointerErrorReason},
+        rust_type_impl::RustType,
+        test_helpers::PartiallyRecognizedValue,
+    };
     use std::convert::TryFrom;
     use test_case::test_case;
 
+    #[test_case(&rust_type!([1]), PrimitiveType::Array ; "array")]
+    #[test_case(&rust_type!(true), PrimitiveType::Boolean ; "boolean")]
+    #[test_case(&rust_type!(1), PrimitiveType::Integer ; "integer")]
+    #[test_case(&rust_type!(null), PrimitiveType::Null ; "null")]
+    #[test_case(&rust_type!(1.5), PrimitiveType::Number ; "number")]
+    #[test_case(&rust_type!({}), PrimitiveType::Object ; "object")]
+    #[test_case(&rust_type!("a"), PrimitiveType::String ; "string")]
+    fn test_try_accessors(value: &RustType, primitive_type: PrimitiveType) {
+        let unexpected = |expected| {
+            if expected == primitive_type {
+                None
+            } else {
+                Some(Error::UnexpectedPrimitiveType { expected, actual: primitive_type })
+            }
+        };
+        assert_eq!(value.try_primitive_type(), Ok(primitive_type));
+        assert_eq!(value.try_as_array().map(|items| items.len()).err(), unexpected(PrimitiveType::Array));
+        assert_eq!(value.try_as_boolean().err(), unexpected(PrimitiveType::Boolean));
+        assert_eq!(value.try_as_integer().err(), unexpected(PrimitiveType::Integer));
+        assert_eq!(value.try_as_null().err(), unexpected(PrimitiveType::Null));
+        assert_eq!(value.try_as_number().err(), unexpected(PrimitiveType::Number));
+        assert_eq!(value.try_as_object().map(|_| ()).err(), unexpected(PrimitiveType::Object));
+        assert_eq!(value.try_as_string().err(), unexpected(PrimitiveType::String));
+        assert_eq!(value.try_to_rust_type().as_ref(), Ok(value));
+    }
+
+    #[test]
+    fn test_try_get_attribute() {
+        let value = rust_type!({"a/b": 1});
+        assert_eq!(value.try_get_attribute("a/b"), Ok(&rust_type!(1)));
+        assert_eq!(
+            value.try_get_attribute("c"),
+            Err(Error::InvalidPointer(PointerError {
+                fragment: "/c".to_string(),
+                component_index: 0,
+                component: "c".to_string(),
+                resolved_fragment: String::new(),
+                primitive_type: PrimitiveType::Object,
+                reason: PointerErrorReason::MissingAttribute,
+            }))
+        );
+        assert_eq!(
+            rust_type!([1]).try_get_attribute("0"),
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: PrimitiveType::Array,
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_get_index() {
+        let value = rust_type!([1, 2]);
+        assert_eq!(value.try_get_index(1), Ok(&rust_type!(2)));
+        assert_eq!(value.try_get_index(2), Err(Error::IndexOutOfBounds { index: 2, length: 2 }));
+        assert_eq!(
+            rust_type!("a").try_get_index(0),
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: PrimitiveType::String,
+            })
+        );
+    }
+
     #[test]
     fn test_ensure_that_jsontype_can_be_made_into_an_object() {
         // The code will fail to compile if JsonType cannot be made into an object
@@ -279,6 +603,15 @@ mod tests {
         assert_eq!(type_str, expected_type_str);
     }
 
+    #[test]
+    fn test_json_map_default_implementation_does_not_panic() {
+        let value = PartiallyRecognizedValue::Integer(1);
+        let json_map = JsonMap::new(&value);
+        assert_eq!(json_map.items().count(), 0);
+        assert_eq!(json_map.keys().count(), 0);
+        assert_eq!(json_map.values().count(), 0);
+    }
+
     #[test]
     fn test_ensure_that_trait_can_be_made_into_an_object() {
         let _: Option<Box<dyn JsonType>> = None;
@@ -296,4 +629,66 @@ mod tests {
         let external_map = rust_type!({"key": {"inner_key": [1, "2"]}});
         assert_eq!(get_fragment(&external_map, fragment), expected_value.as_ref());
     }
+
+    #[test_case("/key/inner_key/1" => Ok(rust_type!("2")) ; "existing fragment")]
+    #[test_case("/not_present" => Err((0, String::new(), PrimitiveType::Object, PointerErrorReason::MissingAttribute)) ; "missing attribute")]
+    #[test_case("/key/inner_key/a" => Err((2, "/key/inner_key".to_string(), PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) ; "invalid array index")]
+    #[test_case("/key/inner_key/01" => Err((2, "/key/inner_key".to_string(), PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) ; "array index with leading zero")]
+    #[test_case("/key/inner_key/+1" => Err((2, "/key/inner_key".to_string(), PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) ; "array index with plus sign")]
+    #[test_case("/key/inner_key/2" => Err((2, "/key/inner_key".to_string(), PrimitiveType::Array, PointerErrorReason::IndexOutOfBounds { length: 2 })) ; "index out of bounds")]
+    #[test_case("/key/inner_key/0/a" => Err((3, "/key/inner_key/0".to_string(), PrimitiveType::Integer, PointerErrorReason::NotAContainer)) ; "scalar value")]
+    fn test_resolve_fragment(fragment: &str) -> Result<RustType, (usize, String, PrimitiveType, PointerErrorReason)> {
+        let external_map = rust_type!({"key": {"inner_key": [1, "2"]}});
+        resolve_fragment(&external_map, fragment).cloned().map_err(|error| match error {
+            Error::InvalidPointer(error) => (error.component_index, error.resolved_fragment, error.primitive_type, error.reason),
+            error => panic!("Unexpected error: {:?}", error),
+        })
+    }
+
+    #[test]
+    fn test_resolve_fragment_through_unrecognized_value() {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Unrecognized]);
+        assert_eq!(resolve_fragment(&value, "/0").map(|_| ()), Ok(()));
+        assert_eq!(resolve_fragment(&value, "/0/0").map(|_| ()), Err(Error::UnrecognizedValue));
+        assert_eq!(value.try_to_rust_type(), Err(Error::UnrecognizedValue));
+    }
+
+    #[test]
+    fn test_pointer_error_message() {
+        let error = resolve_fragment(&rust_type!({"a": [true]}), "/a/b~0c").unwrap_err();
+        assert_eq!(
+            error,
+            Error::InvalidPointer(PointerError {
+                fragment: "/a/b~0c".to_string(),
+                component_index: 1,
+                component: "b~c".to_string(),
+                resolved_fragment: "/a".to_string(),
+                primitive_type: PrimitiveType::Array,
+                reason: PointerErrorReason::InvalidArrayIndex,
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Component #1 (`b~c`) of `/a/b~0c` is not a valid array index (resolved fragment `/a` is of type `Array`)"
+        );
+    }
+
+    #[test_case("", &rust_type!("changed"))]
+    #[test_case("/key", &rust_type!({"key": "changed"}))]
+    #[test_case("/key/inner_key/1", &rust_type!({"key": {"inner_key": [1, "changed"]}}))]
+    fn test_get_fragment_mut(fragment: &str, expected_value: &RustType) {
+        let mut external_map = rust_type!({"key": {"inner_key": [1, "2"]}});
+        *get_fragment_mut(&mut external_map, fragment).unwrap() = rust_type!("changed");
+        assert_eq!(&external_map, expected_value);
+    }
+
+    #[test_case("/not_present")]
+    #[test_case("/key/inner_key/a")]
+    #[test_case("/key/inner_key/2")]
+    #[test_case("/key/inner_key/01")]
+    #[test_case("/key/inner_key/+1")]
+    fn test_get_fragment_mut_not_present(fragment: &str) {
+        let mut external_map = rust_type!({"key": {"inner_key": [1, "2"]}});
+        assert_eq!(get_fragment_mut(&mut external_map, fragment), None);
+    }
 }
@@ -54,15 +54,47 @@ extern crate serde_json;
 // Macros have to be imported first to allow usage on other modules
 #[macro_use]
 pub mod macros;
+pub mod accessors;
 
+pub mod comparison;
+pub mod convert;
+pub mod cursor;
+#[cfg(feature = "serde")]
+mod deserializer;
+pub mod diff;
 mod error;
 pub mod fragment_helpers;
+pub mod hash;
+pub mod json_path;
 mod json_type;
+pub mod merge_patch;
+pub mod parser;
+pub mod patch;
 mod rust_type_impl;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod schema_inference;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod serializer;
+#[cfg(test)]
+mod test_helpers;
 pub mod traits;
+pub mod transform;
+pub mod visitor;
 
+#[cfg(feature = "serde")]
+pub use crate::{
+    deserializer::{from_json_type, DeserializerError, JsonTypeDeserializer},
+    serde_impl::SerializeJsonType,
+};
 pub use crate::{
-    error::Error,
-    json_type::{get_fragment, JsonMap, JsonMapTrait, JsonType, JsonTypeToString, PrimitiveType, ThreadSafeJsonType, ToRustType},
+    error::{Error, PointerError, PointerErrorReason},
+    json_type::{
+        get_fragment, get_fragment_mut, resolve_fragment, JsonMap, JsonMapTrait, JsonType, JsonTypeBuilder, JsonTypeMut, JsonTypeToCanonicalString, JsonTypeToString,
+        PrimitiveType, ThreadSafeJsonType, ToRustType,
+    },
     rust_type_impl::RustType,
 };
+// Re-exported as it is the map type used by RustType::Object
+pub use indexmap::IndexMap;
@@ -190,12 +190,12 @@ macro_rules! rust_type {
     };
 
     ({}) => {
-        $crate::RustType::Object(::std::collections::HashMap::with_capacity(0))
+        $crate::RustType::Object($crate::IndexMap::with_capacity(0))
     };
 
     ({ $($tt:tt)+ }) => {
         $crate::RustType::Object({
-            let mut object = ::std::collections::HashMap::new();
+            let mut object = $crate::IndexMap::new();
             rust_type!(@object object () ($($tt)+) ($($tt)+));
             object
         })
@@ -212,7 +212,7 @@ macro_rules! rust_type {
 #[cfg(test)]
 mod tests {
     use crate::rust_type_impl::RustType;
-    use std::collections::HashMap;
+    use indexmap::IndexMap;
     use test_case::test_case;
 
     #[test_case(rust_type!(null)  => RustType::Null)]
@@ -222,18 +222,18 @@ mod tests {
     #[test_case(rust_type!(true)  => RustType::Boolean(true))]
     #[test_case(rust_type!(false) => RustType::Boolean(false))]
     #[test_case(rust_type!([])    => RustType::List(Vec::new()))]
-    #[test_case(rust_type!({})    => RustType::Object(HashMap::new()))]
+    #[test_case(rust_type!({})    => RustType::Object(IndexMap::new()))]
     // Test not empty lists
     #[test_case(rust_type!([null]) => RustType::List(vec![RustType::Null]))]
     #[test_case(
         rust_type!([{"k": 6}, [5], {}, [], false, true, "4", 2.3, 1, null]) => RustType::List(vec![
             RustType::Object({
-                let mut map = HashMap::new();
+                let mut map = IndexMap::new();
                 let _ = map.insert("k".to_string(), RustType::Integer(6));
                 map
             }),
             RustType::List(vec![RustType::Integer(5)]),
-            RustType::Object(HashMap::new()),
+            RustType::Object(IndexMap::new()),
             RustType::List(Vec::new()),
             RustType::Boolean(false),
             RustType::Boolean(true),
@@ -258,12 +258,12 @@ mod tests {
             "null": null
         }) => RustType::Object([
             ("{\"k\":6}".to_string(), RustType::Object({
-                let mut map = HashMap::new();
+                let mut map = IndexMap::new();
                 let _ = map.insert("k".to_string(), RustType::Integer(6));
                 map
             })),
             ("[5]".to_string(), RustType::List(vec![RustType::Integer(5)])),
-            ("{}".to_string(), RustType::Object(HashMap::new())),
+            ("{}".to_string(), RustType::Object(IndexMap::new())),
             ("[]".to_string(), RustType::List(Vec::new())),
             ("false".to_string(), RustType::Boolean(false)),
             ("true".to_string(), RustType::Boolean(true)),
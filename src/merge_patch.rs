@@ -0,0 +1,147 @@
+// Implementation of JSON Merge Patch (RFC 7386: https://tools.ietf.org/html/rfc7386) on top of JsonType and JsonTypeMut traits
+use crate::{
+    comparison::{json_eq_with, NumericComparison},
+    convert::convert_into,
+    error::Error,
+    json_type::{JsonMapTrait, JsonType, JsonTypeBuilder, JsonTypeMut},
+    rust_type_impl::RustType,
+};
+use indexmap::IndexMap;
+
+/// Apply the merge `patch` to `target`.
+///
+/// NOTE: As defined by RFC 7386 null values in `patch` objects represent the removal of the attribute from `target`
+///
+/// # Errors
+/// `Error::IntegerOutOfRange`, `Error::NonFiniteNumber` or `Error::NonStringKey` if `patch` contains values that `target`
+/// cannot represent (see `convert::convert_into`), `Error::UnrecognizedValue` if `patch` contains values not recognized as
+/// JSON primitive types. Errors reported by the backend while mutating `target` are propagated.
+/// NOTE: `target` is not restored in case of error, so it might be partially patched
+pub fn merge_patch<T: JsonTypeMut + JsonTypeBuilder, P: JsonType>(target: &mut T, patch: &P) -> Result<(), Error> {
+    if let Some(patch_object) = patch.as_object() {
+        if !target.is_object() {
+            let _ = target.replace(T::from_object(Vec::new()));
+        }
+        for (attribute_name, patch_value) in patch_object.items() {
+            if patch_value.is_null() {
+                let _ = target.remove_attribute(attribute_name)?;
+            } else if let Some(target_value) = target.get_attribute_mut(attribute_name) {
+                merge_patch(target_value, patch_value)?;
+            } else {
+                let mut target_value = T::null();
+                merge_patch(&mut target_value, patch_value)?;
+                let _ = target.set_attribute(attribute_name, target_value)?;
+            }
+        }
+    } else {
+        let _ = target.replace(convert_into(patch)?);
+    }
+    Ok(())
+}
+
+/// Generate the merge patch that transforms `before` into `after`.
+///
+/// NOTE: Merge patches cannot represent null values within objects, so `merge_patch(before, create_merge_patch(before, after))`
+/// will not contain object attributes set to null in `after`
+///
+/// # Panics
+/// The function panics if `after` contains values not recognized as JSON primitive types
+#[must_use]
+pub fn create_merge_patch<A: JsonType, B: JsonType>(before: &A, after: &B) -> RustType {
+    if let (Some(before_object), Some(after_object)) = (before.as_object(), after.as_object()) {
+        let mut patch = IndexMap::new();
+        for attribute_name in before_object.keys() {
+            if !after.has_attribute(attribute_name) {
+                let _ = patch.insert(attribute_name.to_string(), RustType::Null);
+            }
+        }
+        for (attribute_name, after_value) in after_object.items() {
+            match before.get_attribute(attribute_name) {
+                Some(before_value) if json_eq_with(before_value, after_value, NumericComparison::Strict) => {}
+                Some(before_value) => {
+                    let _ = patch.insert(attribute_name.to_string(), create_merge_patch(before_value, after_value));
+                }
+                None => {
+                    let _ = patch.insert(attribute_name.to_string(), after_value.to_rust_type());
+                }
+            }
+        }
+        RustType::Object(patch)
+    } else {
+        after.to_rust_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_merge_patch, merge_patch};
+    use crate::{error::Error, rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use test_case::test_case;
+
+    // Examples from RFC 7386 Appendix A
+    #[test_case(rust_type!({"a": "b"}), &rust_type!({"a": "c"}) => rust_type!({"a": "c"}) ; "rfc example 1")]
+    #[test_case(rust_type!({"a": "b"}), &rust_type!({"b": "c"}) => rust_type!({"a": "b", "b": "c"}) ; "rfc example 2")]
+    #[test_case(rust_type!({"a": "b"}), &rust_type!({"a": null}) => rust_type!({}) ; "rfc example 3")]
+    #[test_case(rust_type!({"a": "b", "b": "c"}), &rust_type!({"a": null}) => rust_type!({"b": "c"}) ; "rfc example 4")]
+    #[test_case(rust_type!({"a": ["b"]}), &rust_type!({"a": "c"}) => rust_type!({"a": "c"}) ; "rfc example 5")]
+    #[test_case(rust_type!({"a": "c"}), &rust_type!({"a": ["b"]}) => rust_type!({"a": ["b"]}) ; "rfc example 6")]
+    #[test_case(rust_type!({"a": {"b": "c"}}), &rust_type!({"a": {"b": "d", "c": null}}) => rust_type!({"a": {"b": "d"}}) ; "rfc example 7")]
+    #[test_case(rust_type!({"a": [{"b": "c"}]}), &rust_type!({"a": [1]}) => rust_type!({"a": [1]}) ; "rfc example 8")]
+    #[test_case(rust_type!(["a", "b"]), &rust_type!(["c", "d"]) => rust_type!(["c", "d"]) ; "rfc example 9")]
+    #[test_case(rust_type!({"a": "b"}), &rust_type!(["c"]) => rust_type!(["c"]) ; "rfc example 10")]
+    #[test_case(rust_type!({"a": "foo"}), &rust_type!(null) => rust_type!(null) ; "rfc example 11")]
+    #[test_case(rust_type!({"a": "foo"}), &rust_type!("bar") => rust_type!("bar") ; "rfc example 12")]
+    #[test_case(rust_type!({"e": null}), &rust_type!({"a": 1}) => rust_type!({"e": null, "a": 1}) ; "rfc example 13")]
+    #[test_case(rust_type!([1, 2]), &rust_type!({"a": "b", "c": null}) => rust_type!({"a": "b"}) ; "rfc example 14")]
+    #[test_case(rust_type!({}), &rust_type!({"a": {"bb": {"ccc": null}}}) => rust_type!({"a": {"bb": {}}}) ; "rfc example 15")]
+    fn test_merge_patch(mut target: RustType, patch: &RustType) -> RustType {
+        merge_patch(&mut target, patch).unwrap();
+        target
+    }
+
+    #[test]
+    fn test_merge_patch_with_unrecognized_values() {
+        let mut target = rust_type!({"a": 1, "b": 2});
+        let patch = PartiallyRecognizedValue::Unrecognized;
+        assert_eq!(merge_patch(&mut target, &patch), Err(Error::UnrecognizedValue));
+        assert_eq!(target, rust_type!({"a": 1, "b": 2}));
+    }
+
+    #[test_case(&rust_type!({"a": "b"}), &rust_type!({"a": "b"}) => rust_type!({}) ; "unchanged attribute")]
+    #[test_case(&rust_type!({"a": "b"}), &rust_type!({"a": "c"}) => rust_type!({"a": "c"}) ; "changed attribute")]
+    #[test_case(&rust_type!({"a": "b", "b": "c"}), &rust_type!({"b": "c"}) => rust_type!({"a": null}) ; "removed attribute")]
+    #[test_case(&rust_type!({"a": {"b": "c", "d": "e"}}), &rust_type!({"a": {"b": "f", "d": "e"}}) => rust_type!({"a": {"b": "f"}}) ; "nested object")]
+    #[test_case(&rust_type!({"a": [1]}), &rust_type!({"a": [1, 2]}) => rust_type!({"a": [1, 2]}) ; "changed array")]
+    #[test_case(&rust_type!([1]), &rust_type!({"a": 1}) => rust_type!({"a": 1}) ; "different primitive types")]
+    fn test_create_merge_patch(before: &RustType, after: &RustType) -> RustType {
+        create_merge_patch(before, after)
+    }
+
+    #[test_case(rust_type!({"a": {"b": [1]}, "c": 1}), &rust_type!({"a": {"b": [2], "d": {"e": "f"}}, "g": true}))]
+    #[test_case(rust_type!([1]), &rust_type!({"a": {}}))]
+    fn test_create_merge_patch_roundtrip(mut before: RustType, after: &RustType) {
+        let patch = create_merge_patch(&before, after);
+        merge_patch(&mut before, &patch).unwrap();
+        assert_eq!(&before, after);
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_merge_patch_on_serde_json() {
+        // defaults + environment overlay + user overrides
+        let mut configuration = json!({"server": {"host": "localhost", "port": 8080}, "debug": false});
+        merge_patch(&mut configuration, &rust_type!({"server": {"host": "example.com"}})).unwrap();
+        merge_patch(&mut configuration, &json!({"server": {"port": null}, "debug": true})).unwrap();
+        assert_eq!(configuration, json!({"server": {"host": "example.com"}, "debug": true}));
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_merge_patch_with_values_not_representable_by_the_target() {
+        let mut configuration = json!({"limits": {"max": 1}});
+        assert_eq!(
+            merge_patch(&mut configuration, &rust_type!({"limits": {"max": RustType::from(i128::MAX)}})),
+            Err(Error::IntegerOutOfRange { value: i128::MAX })
+        );
+    }
+}
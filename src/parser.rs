@@ -0,0 +1,420 @@
+// Parser of JSON documents (RFC 8259: https://tools.ietf.org/html/rfc8259) into RustType instances
+use crate::{error::Error, rust_type_impl::RustType};
+use indexmap::IndexMap;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParserOptions {
+    // Maximum amount of nested arrays and objects, it prevents stack overflows while parsing untrusted documents
+    pub max_depth: usize,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
+
+impl ParserOptions {
+    #[must_use]
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+struct Parser<'input> {
+    input: &'input str,
+    position: usize,
+    depth: usize,
+    options: &'input ParserOptions,
+}
+
+impl Parser<'_> {
+    // Line and column (both 1-based) of the current position, the column is expressed in characters
+    fn line_column(&self) -> (usize, usize) {
+        let consumed = &self.input[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().map_or(0, |last_line| last_line.chars().count()) + 1;
+        (line, column)
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        let (line, column) = self.line_column();
+        Error::InvalidJson {
+            line,
+            column,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn expect_byte(&mut self, expected: u8) -> Result<(), Error> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", expected as char)))
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: RustType) -> Result<RustType, Error> {
+        if self.input[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn consume_digits(&mut self) -> usize {
+        let start = self.position;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn parse_number(&mut self) -> Result<RustType, Error> {
+        let start = self.position;
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.position += 1,
+            Some(b'1'..=b'9') => {
+                let _ = self.consume_digits();
+            }
+            _ => return Err(self.error("expected a digit")),
+        }
+        let mut is_integer = true;
+        if self.peek() == Some(b'.') {
+            is_integer = false;
+            self.position += 1;
+            if self.consume_digits() == 0 {
+                return Err(self.error("expected a digit after the decimal point"));
+            }
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            is_integer = false;
+            self.position += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.position += 1;
+            }
+            if self.consume_digits() == 0 {
+                return Err(self.error("expected a digit in the exponent"));
+            }
+        }
+
+        let number_str = &self.input[start..self.position];
+        // Integers that do not fit into i128 are represented as floating point numbers
+        if is_integer {
+            if let Ok(integer) = number_str.parse::<i128>() {
+                return Ok(RustType::Integer(integer));
+            }
+        }
+        match number_str.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(RustType::Number(number)),
+            _ => {
+                self.position = start;
+                Err(self.error("number out of range"))
+            }
+        }
+    }
+
+    fn parse_hex_escape(&mut self) -> Result<u16, Error> {
+        let hex_digits = self
+            .input
+            .get(self.position..self.position + 4)
+            .filter(|hex_digits| hex_digits.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        if let Some(code_unit) = hex_digits.and_then(|hex_digits| u16::from_str_radix(hex_digits, 16).ok()) {
+            self.position += 4;
+            Ok(code_unit)
+        } else {
+            Err(self.error("expected 4 hexadecimal digits"))
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, Error> {
+        let escape_start = self.position - 2;
+        let first_code_unit = self.parse_hex_escape()?;
+        let code_point = if (0xD800..0xDC00).contains(&first_code_unit) {
+            // High surrogate, it has to be followed by an escaped low surrogate
+            if !self.input[self.position..].starts_with("\\u") {
+                self.position = escape_start;
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.position += 2;
+            let second_code_unit = self.parse_hex_escape()?;
+            if !(0xDC00..0xE000).contains(&second_code_unit) {
+                self.position = escape_start;
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((u32::from(first_code_unit) - 0xD800) << 10) + (u32::from(second_code_unit) - 0xDC00)
+        } else {
+            u32::from(first_code_unit)
+        };
+        std::char::from_u32(code_point).ok_or_else(|| {
+            self.position = escape_start;
+            self.error("unpaired surrogate")
+        })
+    }
+
+    fn parse_string(&mut self) -> Result<String, Error> {
+        self.expect_byte(b'"')?;
+        let mut result = String::new();
+        loop {
+            // Characters that do not need unescaping are copied in chunks
+            let chunk_start = self.position;
+            while let Some(byte) = self.peek() {
+                if byte == b'"' || byte == b'\\' || byte < 0x20 {
+                    break;
+                }
+                self.position += 1;
+            }
+            result.push_str(&self.input[chunk_start..self.position]);
+
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(result);
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escaped = self.peek();
+                    self.position += 1;
+                    match escaped {
+                        Some(b'"') => result.push('"'),
+                        Some(b'\\') => result.push('\\'),
+                        Some(b'/') => result.push('/'),
+                        Some(b'b') => result.push('\u{8}'),
+                        Some(b'f') => result.push('\u{c}'),
+                        Some(b'n') => result.push('\n'),
+                        Some(b'r') => result.push('\r'),
+                        Some(b't') => result.push('\t'),
+                        Some(b'u') => result.push(self.parse_unicode_escape()?),
+                        _ => {
+                            self.position -= 2;
+                            return Err(self.error("invalid escape sequence"));
+                        }
+                    }
+                }
+                Some(_) => return Err(self.error("control characters must be escaped")),
+            }
+        }
+    }
+
+    // Parse a comma separated sequence of items (ie. array items or object attributes) terminated by `closing_byte`
+    fn parse_sequence(&mut self, closing_byte: u8, mut parse_item: impl FnMut(&mut Self) -> Result<(), Error>) -> Result<(), Error> {
+        if self.depth == self.options.max_depth {
+            let (line, column) = self.line_column();
+            return Err(Error::MaxDepthExceeded {
+                line,
+                column,
+                max_depth: self.options.max_depth,
+            });
+        }
+        self.depth += 1;
+        self.position += 1;
+        self.skip_whitespaces();
+        if self.peek() == Some(closing_byte) {
+            self.position += 1;
+        } else {
+            loop {
+                self.skip_whitespaces();
+                parse_item(self)?;
+                self.skip_whitespaces();
+                match self.peek() {
+                    Some(b',') => self.position += 1,
+                    Some(byte) if byte == closing_byte => {
+                        self.position += 1;
+                        break;
+                    }
+                    _ => return Err(self.error(&format!("expected `,` or `{}`", closing_byte as char))),
+                }
+            }
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<RustType, Error> {
+        match self.peek() {
+            Some(b'n') => self.parse_literal("null", RustType::Null),
+            Some(b't') => self.parse_literal("true", RustType::Boolean(true)),
+            Some(b'f') => self.parse_literal("false", RustType::Boolean(false)),
+            Some(b'"') => self.parse_string().map(RustType::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(b'[') => {
+                let mut items = Vec::new();
+                self.parse_sequence(b']', |parser| {
+                    items.push(parser.parse_value()?);
+                    Ok(())
+                })?;
+                Ok(RustType::List(items))
+            }
+            Some(b'{') => {
+                let mut attributes = IndexMap::new();
+                self.parse_sequence(b'}', |parser| {
+                    let key = parser.parse_string()?;
+                    parser.skip_whitespaces();
+                    parser.expect_byte(b':')?;
+                    parser.skip_whitespaces();
+                    // As most of the JSON parsers, the last value wins in case of duplicated keys (the position of the first occurrence is preserved)
+                    let _ = attributes.insert(key, parser.parse_value()?);
+                    Ok(())
+                })?;
+                Ok(RustType::Object(attributes))
+            }
+            None => Err(self.error("unexpected end of input")),
+            Some(_) => Err(self.error("expected a value")),
+        }
+    }
+}
+
+/// Parse a JSON document into a `RustType` instance according to `options`
+///
+/// # Errors
+/// `Error::InvalidJson` is returned if `input` is not a valid JSON document, while `Error::MaxDepthExceeded` is returned
+/// if the document contains more nested arrays and objects than `options.max_depth`
+pub fn parse_json_with_options(input: &str, options: &ParserOptions) -> Result<RustType, Error> {
+    let mut parser = Parser {
+        input,
+        position: 0,
+        depth: 0,
+        options,
+    };
+    parser.skip_whitespaces();
+    let value = parser.parse_value()?;
+    parser.skip_whitespaces();
+    if parser.position == input.len() {
+        Ok(value)
+    } else {
+        Err(parser.error("trailing characters"))
+    }
+}
+
+/// Parse a JSON document into a `RustType` instance
+///
+/// # Errors
+/// `Error::InvalidJson` is returned if `input` is not a valid JSON document, while `Error::MaxDepthExceeded` is returned
+/// if the document exceeds the default maximum nesting depth (`ParserOptions::default`)
+pub fn parse_json(input: &str) -> Result<RustType, Error> {
+    parse_json_with_options(input, &ParserOptions::default())
+}
+
+impl FromStr for RustType {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_json, parse_json_with_options, ParserOptions};
+    use crate::{error::Error, rust_type_impl::RustType};
+    use test_case::test_case;
+
+    fn invalid_json(line: usize, column: usize, reason: &str) -> Error {
+        Error::InvalidJson {
+            line,
+            column,
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test_case("null" => Ok(rust_type!(null)))]
+    #[test_case(" true " => Ok(rust_type!(true)))]
+    #[test_case("false" => Ok(rust_type!(false)))]
+    #[test_case("-0" => Ok(rust_type!(0)) ; "negative zero")]
+    #[test_case("170141183460469231731687303715884105727" => Ok(RustType::Integer(i128::MAX)) ; "max i128")]
+    #[test_case("170141183460469231731687303715884105728" => Ok(rust_type!(1.701_411_834_604_692_3e38)) ; "integer out of i128 range")]
+    #[test_case("1.0" => Ok(rust_type!(1.0)) ; "integral number")]
+    #[test_case("-2.5E-3" => Ok(rust_type!(-0.0025)) ; "number with exponent")]
+    #[test_case(r#""a\"\\\/\b\f\n\r\té😀""# => Ok(rust_type!("a\"\\/\u{8}\u{c}\n\r\t\u{e9}\u{1f600}")) ; "escaped string")]
+    #[test_case("\"é😀\"" => Ok(rust_type!("é😀")) ; "non ascii string")]
+    #[test_case("[]" => Ok(rust_type!([])) ; "empty array")]
+    #[test_case(" { } " => Ok(rust_type!({})) ; "empty object")]
+    #[test_case("[1, [2, {\"a\": [null]}]]" => Ok(rust_type!([1, [2, {"a": [null]}]])) ; "nested values")]
+    #[test_case("{\"a\": 1, \"b\": {\"c\": \"d\"}, \"a\": 2}" => Ok(rust_type!({"a": 2, "b": {"c": "d"}})) ; "duplicated keys")]
+    fn test_parse_json(input: &str) -> Result<RustType, Error> {
+        parse_json(input)
+    }
+
+    #[test_case("" => invalid_json(1, 1, "unexpected end of input") ; "empty input")]
+    #[test_case("nul" => invalid_json(1, 1, "expected a value") ; "truncated literal")]
+    #[test_case("[1,]" => invalid_json(1, 4, "expected a value") ; "trailing comma")]
+    #[test_case("[1 2]" => invalid_json(1, 4, "expected `,` or `]`") ; "missing comma")]
+    #[test_case("{\n  \"a\" 1\n}" => invalid_json(2, 7, "expected `:`") ; "missing colon")]
+    #[test_case("{1: 2}" => invalid_json(1, 2, "expected `\"`") ; "non string key")]
+    #[test_case("01" => invalid_json(1, 2, "trailing characters") ; "leading zero")]
+    #[test_case("1." => invalid_json(1, 3, "expected a digit after the decimal point") ; "missing fractional digits")]
+    #[test_case("[1e]" => invalid_json(1, 4, "expected a digit in the exponent") ; "missing exponent digits")]
+    #[test_case("1e400" => invalid_json(1, 1, "number out of range") ; "infinite number")]
+    #[test_case("\"a\nb\"" => invalid_json(1, 3, "control characters must be escaped") ; "unescaped new line")]
+    #[test_case(r#""\x""# => invalid_json(1, 2, "invalid escape sequence") ; "invalid escape")]
+    #[test_case(r#""\u12g4""# => invalid_json(1, 4, "expected 4 hexadecimal digits") ; "invalid unicode escape")]
+    #[test_case(r#"["é", "\ud83d"]"# => invalid_json(1, 8, "unpaired surrogate") ; "unpaired high surrogate")]
+    #[test_case(r#""\ude00""# => invalid_json(1, 2, "unpaired surrogate") ; "unpaired low surrogate")]
+    #[test_case("\"abc" => invalid_json(1, 5, "unterminated string") ; "unterminated string")]
+    #[test_case("{} {}" => invalid_json(1, 4, "trailing characters") ; "trailing characters")]
+    fn test_parse_json_errors(input: &str) -> Error {
+        parse_json(input).unwrap_err()
+    }
+
+    #[test_case("[[1]]", 2 => Ok(rust_type!([[1]])) ; "depth within limit")]
+    #[test_case("[{\"a\": [1]}]", 2 => Err(Error::MaxDepthExceeded { line: 1, column: 8, max_depth: 2 }) ; "depth exceeding limit")]
+    fn test_parse_json_max_depth(input: &str, max_depth: usize) -> Result<RustType, Error> {
+        parse_json_with_options(input, &ParserOptions::default().with_max_depth(max_depth))
+    }
+
+    #[test]
+    fn test_deeply_nested_documents_do_not_overflow_the_stack() {
+        let input = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(
+            parse_json(&input),
+            Err(Error::MaxDepthExceeded {
+                line: 1,
+                column: 129,
+                max_depth: 128
+            })
+        );
+    }
+
+    #[test]
+    fn test_attributes_order_is_preserved() {
+        let input = r#"{"z":1,"a":{"y":2,"b":3},"m":[{"x":4,"c":5}]}"#;
+        assert_eq!(parse_json(input).unwrap().to_string(), input);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("{\"a\": [1, 2.5]}".parse::<RustType>(), Ok(rust_type!({"a": [1, 2.5]})));
+    }
+
+    #[test_case(&rust_type!({"a\u{0}\"": ["\u{2028}\\", 1.5, -3, null, {"b": false}]}))]
+    #[test_case(&rust_type!([i128::MIN, 1e300, "\u{1}\u{10ffff}"]))]
+    fn test_serialization_roundtrip(value: &RustType) {
+        use crate::serializer::{to_json_string_with_options, SerializerOptions};
+
+        for options in &[
+            SerializerOptions::compact(),
+            SerializerOptions::pretty(),
+            SerializerOptions::compact().with_escape_non_ascii(true),
+        ] {
+            assert_eq!(&parse_json(&to_json_string_with_options(value, options).unwrap()).unwrap(), value);
+        }
+    }
+}
@@ -0,0 +1,431 @@
+// Implementation of JSON Patch (RFC 6902: https://tools.ietf.org/html/rfc6902) on top of JsonType and JsonTypeMut traits
+use crate::{
+    comparison::json_eq,
+    convert::convert_into,
+    error::{Error, PointerError, PointerErrorReason},
+    fragment_helpers::{array_index_from_fragment_part, fragment_components_from_fragment, fragment_from_fragment_components},
+    json_type::{get_fragment_mut, resolve_fragment, JsonType, JsonTypeBuilder, JsonTypeMut, PrimitiveType},
+};
+
+#[derive(Clone, Debug, Eq, IntoStaticStr, PartialEq)]
+#[strum(serialize_all = "lowercase")]
+pub enum PatchOperation<T> {
+    Add { path: String, value: T },
+    Remove { path: String },
+    Replace { path: String, value: T },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: T },
+}
+
+impl<T> PatchOperation<T> {
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. } | Self::Remove { path } | Self::Replace { path, .. } | Self::Move { path, .. } | Self::Copy { path, .. } | Self::Test { path, .. } => path,
+        }
+    }
+}
+
+fn is_descendant(fragment: &str, ancestor_fragment: &str) -> bool {
+    fragment.starts_with(ancestor_fragment) && fragment[ancestor_fragment.len()..].starts_with('/')
+}
+
+fn parse_operation<T: JsonType + Clone>(index: usize, operation: &T) -> Result<PatchOperation<T>, Error> {
+    let invalid_operation = |reason: String| Error::InvalidPatchOperation { index, reason };
+    if !operation.is_object() {
+        return Err(invalid_operation(format!("expected an object, found `{}`", operation.try_primitive_type()?)));
+    }
+
+    let string_member = |member_name: &str| {
+        operation
+            .get_attribute(member_name)
+            .ok_or_else(|| invalid_operation(format!("missing `{member_name}` member")))
+            .and_then(|value| value.as_string().ok_or_else(|| invalid_operation(format!("`{member_name}` member must be a string"))))
+            .map(ToString::to_string)
+    };
+    let value_member = || {
+        operation
+            .get_attribute("value")
+            .cloned()
+            .ok_or_else(|| invalid_operation("missing `value` member".to_string()))
+    };
+
+    let path = string_member("path")?;
+    match string_member("op")?.as_str() {
+        "add" => Ok(PatchOperation::Add { path, value: value_member()? }),
+        "remove" => {
+            if path.is_empty() {
+                Err(invalid_operation("the whole document cannot be removed".to_string()))
+            } else {
+                Ok(PatchOperation::Remove { path })
+            }
+        }
+        "replace" => Ok(PatchOperation::Replace { path, value: value_member()? }),
+        "move" => {
+            let from = string_member("from")?;
+            if is_descendant(&path, &from) {
+                Err(invalid_operation(format!("`{from}` cannot be moved into one of its children")))
+            } else {
+                Ok(PatchOperation::Move { from, path })
+            }
+        }
+        "copy" => Ok(PatchOperation::Copy {
+            from: string_member("from")?,
+            path,
+        }),
+        "test" => Ok(PatchOperation::Test { path, value: value_member()? }),
+        operation_name => Err(invalid_operation(format!("unsupported operation `{operation_name}`"))),
+    }
+}
+
+/// Parse a JSON Patch document (an array of operation objects)
+///
+/// # Errors
+///
+/// `Error::UnexpectedPrimitiveType` if `patch` is not an array and `Error::InvalidPatchOperation` if any of the operations is malformed.
+/// `Error::UnrecognizedValue` if `patch`, or any of its operations, is not recognized as a JSON primitive type.
+pub fn parse_patch<T: JsonType + Clone>(patch: &T) -> Result<Vec<PatchOperation<T>>, Error> {
+    let Some(operations) = patch.as_array() else {
+        return Err(Error::UnexpectedPrimitiveType {
+            expected: PrimitiveType::Array,
+            actual: patch.try_primitive_type()?,
+        });
+    };
+    operations.enumerate().map(|(index, operation)| parse_operation(index, operation)).collect()
+}
+
+// Error for the last component of `path` (added or removed), consistent with the errors reported by `resolve_fragment`
+fn invalid_last_component(path: &str, fragment_components: &[String], primitive_type: PrimitiveType, reason: PointerErrorReason) -> Error {
+    let component_index = fragment_components.len() - 1;
+    Error::InvalidPointer(PointerError {
+        fragment: path.to_string(),
+        component_index,
+        component: fragment_components[component_index].clone(),
+        resolved_fragment: fragment_from_fragment_components(&fragment_components[..component_index]),
+        primitive_type,
+        reason,
+    })
+}
+
+fn get_existing_mut<'json, T: JsonTypeMut>(json_object: &'json mut T, fragment: &str) -> Result<&'json mut T, Error> {
+    // `get_fragment_mut` does not report why the resolution failed, so the fragment is resolved first via `resolve_fragment`
+    let _ = resolve_fragment(json_object, fragment)?;
+    get_fragment_mut(json_object, fragment).ok_or_else(|| Error::FragmentNotFound { fragment: fragment.to_string() })
+}
+
+fn add<T: JsonTypeMut>(json_object: &mut T, path: &str, value: T) -> Result<(), Error> {
+    let fragment_components = fragment_components_from_fragment(path).collect::<Vec<_>>();
+    if let Some((last_fragment_component, parent_fragment_components)) = fragment_components.split_last() {
+        let parent = get_existing_mut(json_object, &fragment_from_fragment_components(parent_fragment_components))?;
+        match parent.try_primitive_type()? {
+            PrimitiveType::Object => parent.set_attribute(last_fragment_component, value).map(|_| ()),
+            PrimitiveType::Array => {
+                let length = parent.as_array().map_or(0, |items| items.len());
+                let index = if last_fragment_component == "-" {
+                    length
+                } else {
+                    array_index_from_fragment_part(last_fragment_component)
+                        .ok_or_else(|| invalid_last_component(path, &fragment_components, PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex))?
+                };
+                // Items can be appended, so the index could be equal to the array length
+                if index > length {
+                    Err(invalid_last_component(
+                        path,
+                        &fragment_components,
+                        PrimitiveType::Array,
+                        PointerErrorReason::IndexOutOfBounds { length },
+                    ))
+                } else {
+                    parent.insert_index(index, value)
+                }
+            }
+            primitive_type => Err(invalid_last_component(path, &fragment_components, primitive_type, PointerErrorReason::NotAContainer)),
+        }
+    } else {
+        let _ = json_object.replace(value);
+        Ok(())
+    }
+}
+
+fn remove<T: JsonTypeMut>(json_object: &mut T, path: &str) -> Result<T, Error> {
+    // The removed value has to exist, so `resolve_fragment` reports why `path` is invalid
+    let _ = resolve_fragment(json_object, path)?;
+    let fragment_components = fragment_components_from_fragment(path).collect::<Vec<_>>();
+    // The whole document cannot be removed (`parse_patch` rejects such operations)
+    let (last_fragment_component, parent_fragment_components) = fragment_components.split_last().ok_or_else(|| Error::FragmentNotFound { fragment: path.to_string() })?;
+    let parent = get_existing_mut(json_object, &fragment_from_fragment_components(parent_fragment_components))?;
+    let removed = if parent.is_array() {
+        match array_index_from_fragment_part(last_fragment_component) {
+            Some(index) => Some(parent.remove_index(index)?),
+            None => None,
+        }
+    } else {
+        parent.remove_attribute(last_fragment_component)?
+    };
+    removed.ok_or_else(|| Error::FragmentNotFound { fragment: path.to_string() })
+}
+
+// Operation values are converted into the backend of `json_object`, so patches can be applied across backends.
+// Errors are reported with the pointer that could not be applied (ie. `from` if the moved value does not exist)
+fn apply_operation<'operation, T: JsonTypeMut + JsonTypeBuilder + Clone, V: JsonType>(
+    json_object: &mut T,
+    operation: &'operation PatchOperation<V>,
+) -> Result<(), (&'operation str, Box<Error>)> {
+    let on = |pointer: &'operation str| move |error: Error| (pointer, Box::new(error));
+    match operation {
+        PatchOperation::Add { path, value } => add(json_object, path, convert_into(value).map_err(on(path))?).map_err(on(path)),
+        PatchOperation::Remove { path } => remove(json_object, path).map(|_| ()).map_err(on(path)),
+        PatchOperation::Replace { path, value } => {
+            let value = convert_into(value).map_err(on(path))?;
+            let _ = get_existing_mut(json_object, path).map_err(on(path))?.replace(value);
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if from == path {
+                resolve_fragment(json_object, from).map(|_| ()).map_err(on(from))
+            } else {
+                let value = remove(json_object, from).map_err(on(from))?;
+                add(json_object, path, value).map_err(on(path))
+            }
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = resolve_fragment(json_object, from).map_err(on(from))?.clone();
+            add(json_object, path, value).map_err(on(path))
+        }
+        PatchOperation::Test { path, value } => {
+            // As defined by RFC 6902 numeric values are equal if they represent the same value (ie. `1` and `1.0`)
+            if json_eq(resolve_fragment(json_object, path).map_err(on(path))?, value) {
+                Ok(())
+            } else {
+                Err((path, Box::new(Error::PatchTestFailed)))
+            }
+        }
+    }
+}
+
+/// Apply the JSON Patch `operations` to `json_object`.
+/// The patch is applied atomically, so `json_object` is left untouched if any of the operations fails.
+///
+/// The operations could be parsed from any backend, their values are converted into the backend of `json_object`.
+///
+/// # Errors
+///
+/// `Error::PatchOperationFailed` reporting the index of the failing operation, the pointer that could not be applied
+/// (`from` for move and copy operations referencing nonexistent values) and the reason of the failure (including
+/// `Error::InvalidPointer` for invalid pointers and values that cannot be exactly represented by the backend of `json_object`).
+pub fn apply_patch<T: JsonTypeMut + JsonTypeBuilder + Clone, V: JsonType>(json_object: &mut T, operations: &[PatchOperation<V>]) -> Result<(), Error> {
+    let mut patched_json_object = json_object.clone();
+    for (index, operation) in operations.iter().enumerate() {
+        apply_operation(&mut patched_json_object, operation).map_err(|(pointer, reason)| Error::PatchOperationFailed {
+            index,
+            operation: operation.into(),
+            pointer: pointer.to_string(),
+            reason,
+        })?;
+    }
+    *json_object = patched_json_object;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_patch, parse_patch, PatchOperation};
+    use crate::{
+        error::{Error, PointerError, PointerErrorReason},
+        fragment_helpers::{fragment_components_from_fragment, fragment_from_fragment_components},
+        json_type::PrimitiveType,
+        rust_type_impl::RustType,
+        test_helpers::PartiallyRecognizedValue,
+    };
+    use test_case::test_case;
+
+    // Error reported for the last component of `fragment`
+    fn invalid_pointer(fragment: &str, primitive_type: PrimitiveType, reason: PointerErrorReason) -> Error {
+        let mut fragment_components = fragment_components_from_fragment(fragment).collect::<Vec<_>>();
+        let component = fragment_components.pop().unwrap();
+        Error::InvalidPointer(PointerError {
+            fragment: fragment.to_string(),
+            component_index: fragment_components.len(),
+            component,
+            resolved_fragment: fragment_from_fragment_components(&fragment_components),
+            primitive_type,
+            reason,
+        })
+    }
+
+    #[test_case(&rust_type!([]) => Ok(vec![]))]
+    #[test_case(
+        &rust_type!([{"op": "add", "path": "/a", "value": 1}, {"op": "move", "from": "/a", "path": "/b"}]) => Ok(vec![
+            PatchOperation::Add { path: "/a".to_string(), value: rust_type!(1) },
+            PatchOperation::Move { from: "/a".to_string(), path: "/b".to_string() },
+        ])
+    )]
+    #[test_case(&rust_type!({}) => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::Object }))]
+    #[test_case(&rust_type!([1]) => Err(Error::InvalidPatchOperation { index: 0, reason: "expected an object, found `Integer`".to_string() }))]
+    #[test_case(&rust_type!([{"path": "/a"}]) => Err(Error::InvalidPatchOperation { index: 0, reason: "missing `op` member".to_string() }))]
+    #[test_case(&rust_type!([{"op": "add", "path": 1}]) => Err(Error::InvalidPatchOperation { index: 0, reason: "`path` member must be a string".to_string() }))]
+    #[test_case(&rust_type!([{"op": "test", "path": ""}, {"op": "add", "path": "/a"}]) => Err(Error::InvalidPatchOperation { index: 0, reason: "missing `value` member".to_string() }))]
+    #[test_case(&rust_type!([{"op": "remove", "path": ""}]) => Err(Error::InvalidPatchOperation { index: 0, reason: "the whole document cannot be removed".to_string() }))]
+    #[test_case(&rust_type!([{"op": "move", "from": "/a", "path": "/a/b"}]) => Err(Error::InvalidPatchOperation { index: 0, reason: "`/a` cannot be moved into one of its children".to_string() }))]
+    #[test_case(&rust_type!([{"op": "unknown", "path": "/a"}]) => Err(Error::InvalidPatchOperation { index: 0, reason: "unsupported operation `unknown`".to_string() }))]
+    fn test_parse_patch(patch: &RustType) -> Result<Vec<PatchOperation<RustType>>, Error> {
+        parse_patch(patch)
+    }
+
+    #[test_case(&PartiallyRecognizedValue::Unrecognized ; "unrecognized patch")]
+    #[test_case(&PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Unrecognized]) ; "unrecognized operation")]
+    fn test_parse_patch_with_unrecognized_values(patch: &PartiallyRecognizedValue) {
+        assert_eq!(parse_patch(patch), Err(Error::UnrecognizedValue));
+    }
+
+    // Examples from RFC 6902 Appendix A
+    #[test_case(rust_type!({"foo": "bar"}), &rust_type!([{"op": "add", "path": "/baz", "value": "qux"}]) => Ok(rust_type!({"baz": "qux", "foo": "bar"})) ; "A.1 adding an object member")]
+    #[test_case(rust_type!({"foo": ["bar", "baz"]}), &rust_type!([{"op": "add", "path": "/foo/1", "value": "qux"}]) => Ok(rust_type!({"foo": ["bar", "qux", "baz"]})) ; "A.2 adding an array element")]
+    #[test_case(rust_type!({"baz": "qux", "foo": "bar"}), &rust_type!([{"op": "remove", "path": "/baz"}]) => Ok(rust_type!({"foo": "bar"})) ; "A.3 removing an object member")]
+    #[test_case(rust_type!({"foo": ["bar", "qux", "baz"]}), &rust_type!([{"op": "remove", "path": "/foo/1"}]) => Ok(rust_type!({"foo": ["bar", "baz"]})) ; "A.4 removing an array element")]
+    #[test_case(rust_type!({"baz": "qux", "foo": "bar"}), &rust_type!([{"op": "replace", "path": "/baz", "value": "boo"}]) => Ok(rust_type!({"baz": "boo", "foo": "bar"})) ; "A.5 replacing a value")]
+    #[test_case(
+        rust_type!({"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}}),
+        &rust_type!([{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}])
+        => Ok(rust_type!({"foo": {"bar": "baz"}, "qux": {"corge": "grault", "thud": "fred"}}))
+        ; "A.6 moving a value"
+    )]
+    #[test_case(rust_type!({"foo": ["all", "grass", "cows", "eat"]}), &rust_type!([{"op": "move", "from": "/foo/1", "path": "/foo/3"}]) => Ok(rust_type!({"foo": ["all", "cows", "eat", "grass"]})) ; "A.7 moving an array element")]
+    #[test_case(
+        rust_type!({"baz": "qux", "foo": ["a", 2, "c"]}),
+        &rust_type!([{"op": "test", "path": "/baz", "value": "qux"}, {"op": "test", "path": "/foo/1", "value": 2}])
+        => Ok(rust_type!({"baz": "qux", "foo": ["a", 2, "c"]}))
+        ; "A.8 testing a value success"
+    )]
+    #[test_case(rust_type!({"foo": 1}), &rust_type!([{"op": "test", "path": "/foo", "value": 1.0}]) => Ok(rust_type!({"foo": 1})) ; "testing numerically equal values")]
+    #[test_case(rust_type!({"foo": "bar"}), &rust_type!([{"op": "add", "path": "/child", "value": {"grandchild": {}}}]) => Ok(rust_type!({"foo": "bar", "child": {"grandchild": {}}})) ; "A.10 adding a nested member object")]
+    #[test_case(rust_type!({"foo": ["bar"]}), &rust_type!([{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]) => Ok(rust_type!({"foo": ["bar", ["abc", "def"]]})) ; "A.16 adding an array value")]
+    #[test_case(rust_type!({"foo": {"bar": 1}}), &rust_type!([{"op": "copy", "from": "/foo", "path": "/baz"}]) => Ok(rust_type!({"foo": {"bar": 1}, "baz": {"bar": 1}})) ; "copying a value")]
+    #[test_case(rust_type!({"foo": 1}), &rust_type!([{"op": "replace", "path": "", "value": [1]}]) => Ok(rust_type!([1])) ; "replacing the whole document")]
+    #[test_case(
+        rust_type!({"baz": "qux"}),
+        &rust_type!([{"op": "test", "path": "/baz", "value": "bar"}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "test", pointer: "/baz".to_string(), reason: Box::new(Error::PatchTestFailed) })
+        ; "A.9 testing a value error"
+    )]
+    #[test_case(
+        rust_type!({"foo": "bar"}),
+        &rust_type!([{"op": "add", "path": "/baz", "value": 1}, {"op": "add", "path": "/baz/bat", "value": "qux"}])
+        => Err(Error::PatchOperationFailed {
+            index: 1,
+            operation: "add",
+            pointer: "/baz/bat".to_string(),
+            reason: Box::new(invalid_pointer("/baz/bat", PrimitiveType::Integer, PointerErrorReason::NotAContainer)),
+        })
+        ; "A.12 adding to a nonexistent target"
+    )]
+    #[test_case(
+        rust_type!({"foo": "bar"}),
+        &rust_type!([{"op": "remove", "path": "/baz"}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "remove", pointer: "/baz".to_string(), reason: Box::new(invalid_pointer("/baz", PrimitiveType::Object, PointerErrorReason::MissingAttribute)) })
+        ; "removing a nonexistent member"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "add", "path": "/foo/01", "value": 1}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "add", pointer: "/foo/01".to_string(), reason: Box::new(invalid_pointer("/foo/01", PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) })
+        ; "adding with an invalid array index"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "remove", "path": "/foo/00"}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "remove", pointer: "/foo/00".to_string(), reason: Box::new(invalid_pointer("/foo/00", PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) })
+        ; "removing with an invalid array index"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "replace", "path": "/foo/00", "value": 1}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "replace", pointer: "/foo/00".to_string(), reason: Box::new(invalid_pointer("/foo/00", PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) })
+        ; "replacing with an invalid array index"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "test", "path": "/foo/00", "value": "bar"}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "test", pointer: "/foo/00".to_string(), reason: Box::new(invalid_pointer("/foo/00", PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) })
+        ; "testing with an invalid array index"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "move", "from": "/foo/+0", "path": "/baz"}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "move", pointer: "/foo/+0".to_string(), reason: Box::new(invalid_pointer("/foo/+0", PrimitiveType::Array, PointerErrorReason::InvalidArrayIndex)) })
+        ; "moving from an invalid array index"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "copy", "from": "/bar", "path": "/baz"}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "copy", pointer: "/bar".to_string(), reason: Box::new(invalid_pointer("/bar", PrimitiveType::Object, PointerErrorReason::MissingAttribute)) })
+        ; "copying from a nonexistent member"
+    )]
+    #[test_case(
+        rust_type!({"foo": ["bar"]}),
+        &rust_type!([{"op": "add", "path": "/foo/2", "value": 1}])
+        => Err(Error::PatchOperationFailed { index: 0, operation: "add", pointer: "/foo/2".to_string(), reason: Box::new(invalid_pointer("/foo/2", PrimitiveType::Array, PointerErrorReason::IndexOutOfBounds { length: 1 })) })
+        ; "adding out of the array bounds"
+    )]
+    fn test_apply_patch(mut json_object: RustType, patch: &RustType) -> Result<RustType, Error> {
+        let original_json_object = json_object.clone();
+        let result = apply_patch(&mut json_object, &parse_patch(patch).unwrap());
+        if result.is_err() {
+            // Failing patches should not modify the object
+            assert_eq!(json_object, original_json_object);
+        }
+        result.map(|()| json_object)
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_apply_patch_on_serde_json() {
+        let mut json_object = json!({"foo": ["bar"]});
+        let patch = json!([{"op": "add", "path": "/foo/0", "value": "baz"}, {"op": "copy", "from": "/foo", "path": "/qux"}]);
+        apply_patch(&mut json_object, &parse_patch(&patch).unwrap()).unwrap();
+        assert_eq!(json_object, json!({"foo": ["baz", "bar"], "qux": ["baz", "bar"]}));
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_apply_serde_json_patch_on_rust_type() {
+        let mut json_object = rust_type!({"foo": ["bar"]});
+        let patch = json!([
+            {"op": "test", "path": "/foo/0", "value": "bar"},
+            {"op": "add", "path": "/baz", "value": {"qux": [1, 2.5]}},
+            {"op": "replace", "path": "/foo/0", "value": null},
+        ]);
+        apply_patch(&mut json_object, &parse_patch(&patch).unwrap()).unwrap();
+        assert_eq!(json_object, rust_type!({"foo": [null], "baz": {"qux": [1, 2.5]}}));
+    }
+}
+
+#[cfg(all(test, feature = "trait_serde_json", feature = "trait_serde_yaml"))]
+mod tests_serde_yaml {
+    use super::{apply_patch, parse_patch};
+    use crate::{error::Error, rust_type_impl::RustType};
+
+    #[test]
+    fn test_apply_serde_json_patch_on_serde_yaml() {
+        let mut yaml_object: serde_yaml::Value = serde_yaml::from_str("foo: [bar]").unwrap();
+        let patch = json!([{"op": "add", "path": "/foo/-", "value": {"baz": true}}, {"op": "test", "path": "/foo/1/baz", "value": true}]);
+        apply_patch(&mut yaml_object, &parse_patch(&patch).unwrap()).unwrap();
+        assert_eq!(yaml_object, serde_yaml::from_str::<serde_yaml::Value>("foo: [bar, {baz: true}]").unwrap());
+    }
+
+    #[test]
+    fn test_apply_patch_with_values_not_representable_by_the_target() {
+        let mut yaml_object: serde_yaml::Value = serde_yaml::from_str("foo: 1").unwrap();
+        let patch = rust_type!([{"op": "replace", "path": "/foo", "value": RustType::from(i128::MAX)}]);
+        assert_eq!(
+            apply_patch(&mut yaml_object, &parse_patch(&patch).unwrap()),
+            Err(Error::PatchOperationFailed {
+                index: 0,
+                operation: "replace",
+                pointer: "/foo".to_string(),
+                reason: Box::new(Error::IntegerOutOfRange { value: i128::MAX }),
+            })
+        );
+    }
+}
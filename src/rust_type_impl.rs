@@ -1,8 +1,10 @@
 use crate::{
-    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeToString, ToRustType},
+    error::Error,
+    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeBuilder, JsonTypeMut, JsonTypeToString, PrimitiveType, ToRustType},
+    serializer::{write_json_fmt, NonFiniteNumberPolicy, SerializerOptions},
     ThreadSafeJsonType,
 };
-use join_lazy_fmt::Join;
+use indexmap::IndexMap;
 use std::{collections::hash_map::HashMap, fmt, ops::Deref};
 
 #[allow(clippy::module_name_repetitions)]
@@ -14,28 +16,14 @@ pub enum RustType {
     Integer(i128),
     Number(f64),
     List(Vec<RustType>),
-    Object(HashMap<String, RustType>),
+    // Objects preserve the insertion order of their attributes
+    Object(IndexMap<String, RustType>),
 }
 
 impl fmt::Display for RustType {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        match self {
-            Self::Null => write!(formatter, "null"),
-            Self::Boolean(value) => write!(formatter, "{}", value),
-            Self::String(value) => write!(formatter, r#""{}""#, value),
-            Self::Integer(value) => write!(formatter, "{}", value),
-            Self::Number(value) => write!(formatter, "{}", value),
-            Self::List(value) => {
-                write!(formatter, "[")?;
-                write!(formatter, "{}", ",".join(value))?;
-                write!(formatter, "]")
-            }
-            Self::Object(value) => {
-                write!(formatter, "{{")?;
-                write!(formatter, "{}", ",".join(value.iter().map(|(key, value)| { format!(r#""{}":{}"#, key, value) })))?;
-                write!(formatter, "}}")
-            }
-        }
+        let options = SerializerOptions::compact().with_non_finite_numbers(NonFiniteNumberPolicy::Null);
+        write_json_fmt(self, formatter, &options).map_err(|_| fmt::Error)
     }
 }
 
@@ -115,10 +103,17 @@ impl From<f64> for RustType {
     }
 }
 
+impl From<IndexMap<String, RustType>> for RustType {
+    #[must_use]
+    fn from(value: IndexMap<String, Self>) -> Self {
+        Self::Object(value)
+    }
+}
+
 impl From<HashMap<String, RustType>> for RustType {
     #[must_use]
     fn from(value: HashMap<String, Self>) -> Self {
-        Self::Object(value)
+        Self::Object(value.into_iter().collect())
     }
 }
 
@@ -133,6 +128,10 @@ impl ToRustType for RustType {
     fn to_rust_type(&self) -> RustType {
         self.clone()
     }
+
+    fn try_to_rust_type(&self) -> Result<RustType, Error> {
+        Ok(self.clone())
+    }
 }
 
 impl JsonType for RustType {
@@ -220,6 +219,119 @@ impl JsonType for RustType {
 
 impl ThreadSafeJsonType for RustType {}
 
+impl JsonTypeMut for RustType {
+    fn get_attribute_mut(&mut self, attribute_name: &str) -> Option<&mut Self> {
+        if let Self::Object(object) = self {
+            object.get_mut(attribute_name)
+        } else {
+            None
+        }
+    }
+
+    fn get_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        if let Self::List(array) = self {
+            array.get_mut(index)
+        } else {
+            None
+        }
+    }
+
+    fn set_attribute(&mut self, attribute_name: &str, value: Self) -> Result<Option<Self>, Error> {
+        if let Self::Object(object) = self {
+            Ok(object.insert(attribute_name.to_string(), value))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: self.primitive_type(),
+            })
+        }
+    }
+
+    fn remove_attribute(&mut self, attribute_name: &str) -> Result<Option<Self>, Error> {
+        if let Self::Object(object) = self {
+            Ok(object.shift_remove(attribute_name))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: self.primitive_type(),
+            })
+        }
+    }
+
+    fn push(&mut self, value: Self) -> Result<(), Error> {
+        if let Self::List(array) = self {
+            array.push(value);
+            Ok(())
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: self.primitive_type(),
+            })
+        }
+    }
+
+    fn insert_index(&mut self, index: usize, value: Self) -> Result<(), Error> {
+        if let Self::List(array) = self {
+            if index > array.len() {
+                Err(Error::IndexOutOfBounds { index, length: array.len() })
+            } else {
+                array.insert(index, value);
+                Ok(())
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: self.primitive_type(),
+            })
+        }
+    }
+
+    fn remove_index(&mut self, index: usize) -> Result<Self, Error> {
+        if let Self::List(array) = self {
+            if index < array.len() {
+                Ok(array.remove(index))
+            } else {
+                Err(Error::IndexOutOfBounds { index, length: array.len() })
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: self.primitive_type(),
+            })
+        }
+    }
+}
+
+impl JsonTypeBuilder for RustType {
+    fn null() -> Self {
+        Self::Null
+    }
+
+    fn from_bool(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+
+    fn from_i128(value: i128) -> Self {
+        Self::Integer(value)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        Self::Number(value)
+    }
+
+    fn from_string(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+
+    fn from_array<I: IntoIterator<Item = Self>>(items: I) -> Self {
+        Self::List(items.into_iter().collect())
+    }
+
+    fn from_object<I: IntoIterator<Item = (String, Self)>>(items: I) -> Self {
+        Self::Object(items.into_iter().collect())
+    }
+}
+
 impl<'json> JsonMapTrait<'json, RustType> for JsonMap<'json, RustType> {
     #[must_use]
     fn items(&'json self) -> Box<dyn Iterator<Item = (&str, &RustType)> + 'json> {
@@ -229,10 +341,8 @@ impl<'json> JsonMapTrait<'json, RustType> for JsonMap<'json, RustType> {
         } {
             Box::new(hash_map.iter().map(|(k, v)| (k.as_str(), v)))
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            // `JsonMap` wrapping a value that is not an object (ie. via `JsonMap::new`) has no entries
+            Box::new(std::iter::empty())
         }
     }
 }
@@ -252,6 +362,7 @@ mod smoke_test {
     #[test_case(&rust_type!(1) => "1")]
     #[test_case(&rust_type!(2.3) => "2.3")]
     #[test_case(&rust_type!([1, 2.3, false]) => "[1,2.3,false]")]
+    #[test_case(&rust_type!({"b": 1, "a": [2], "c": {"e": null, "d": true}}) => r#"{"b":1,"a":[2],"c":{"e":null,"d":true}}"# ; "object attributes order")]
     fn test_to_string(value: &RustType) -> String {
         value.to_string()
     }
@@ -375,3 +486,84 @@ mod tests_to_json_string {
         );
     }
 }
+
+#[cfg(test)]
+mod tests_json_type_mut {
+    use crate::{
+        error::Error,
+        json_type::{JsonTypeMut, PrimitiveType},
+        rust_type_impl::RustType,
+    };
+    use test_case::test_case;
+
+    #[test]
+    fn test_get_attribute_mut() {
+        let mut value = rust_type!({"key": 1});
+        *value.get_attribute_mut("key").unwrap() = rust_type!("changed");
+        assert_eq!(value, rust_type!({"key": "changed"}));
+        assert_eq!(value.get_attribute_mut("not-present"), None);
+    }
+
+    #[test]
+    fn test_get_index_mut() {
+        let mut value = rust_type!([1, 2]);
+        *value.get_index_mut(1).unwrap() = rust_type!(null);
+        assert_eq!(value, rust_type!([1, null]));
+        assert_eq!(value.get_index_mut(2), None);
+    }
+
+    #[test_case(rust_type!({}), "key" => Ok(None))]
+    #[test_case(rust_type!({"key": 1}), "key" => Ok(Some(rust_type!(1))))]
+    #[test_case(rust_type!([]), "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Array }))]
+    fn test_set_attribute(mut value: RustType, attribute_name: &str) -> Result<Option<RustType>, Error> {
+        let result = value.set_attribute(attribute_name, rust_type!("new"))?;
+        assert_eq!(value, rust_type!({"key": "new"}));
+        Ok(result)
+    }
+
+    #[test_case(rust_type!({"key": 1}), "key" => Ok(Some(rust_type!(1))))]
+    #[test_case(rust_type!({"key": 1}), "not-present" => Ok(None))]
+    #[test_case(rust_type!(1), "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Integer }))]
+    fn test_remove_attribute(mut value: RustType, attribute_name: &str) -> Result<Option<RustType>, Error> {
+        value.remove_attribute(attribute_name)
+    }
+
+    #[test]
+    fn test_attributes_order_is_preserved() {
+        let mut value = rust_type!({"c": 1, "a": 2, "b": 3});
+        let _ = value.remove_attribute("a").unwrap();
+        let _ = value.set_attribute("c", rust_type!(4)).unwrap();
+        let _ = value.set_attribute("a", rust_type!(5)).unwrap();
+        assert_eq!(value.to_string(), r#"{"c":4,"b":3,"a":5}"#);
+    }
+
+    #[test_case(rust_type!([1]) => Ok(rust_type!([1, "new"])))]
+    #[test_case(rust_type!({}) => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::Object }))]
+    fn test_push(mut value: RustType) -> Result<RustType, Error> {
+        value.push(rust_type!("new"))?;
+        Ok(value)
+    }
+
+    #[test_case(rust_type!([1, 2]), 0 => Ok(rust_type!(["new", 1, 2])))]
+    #[test_case(rust_type!([1, 2]), 2 => Ok(rust_type!([1, 2, "new"])))]
+    #[test_case(rust_type!([1, 2]), 3 => Err(Error::IndexOutOfBounds { index: 3, length: 2 }))]
+    #[test_case(rust_type!(null), 0 => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::Null }))]
+    fn test_insert_index(mut value: RustType, index: usize) -> Result<RustType, Error> {
+        value.insert_index(index, rust_type!("new"))?;
+        Ok(value)
+    }
+
+    #[test_case(rust_type!([1, 2]), 0 => Ok(rust_type!(1)))]
+    #[test_case(rust_type!([1, 2]), 2 => Err(Error::IndexOutOfBounds { index: 2, length: 2 }))]
+    #[test_case(rust_type!("string"), 0 => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::String }))]
+    fn test_remove_index(mut value: RustType, index: usize) -> Result<RustType, Error> {
+        value.remove_index(index)
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut value = rust_type!({"key": [1]});
+        assert_eq!(value.get_attribute_mut("key").unwrap().replace(rust_type!(2)), rust_type!([1]));
+        assert_eq!(value, rust_type!({"key": 2}));
+    }
+}
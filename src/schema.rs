@@ -0,0 +1,1296 @@
+// JSON Schema (Draft 7 and Draft 2020-12) validation of any JsonType instance.
+// The schema is compiled once into a graph of nodes: `$ref`s are resolved at compile time (local references via
+// `get_fragment`, external documents via a `SchemaResolver`) so the validation never looks up references.
+// `format` and the content keywords are annotations only, as suggested by the specifications.
+// Dynamic references (`$dynamicRef`, `$recursiveRef`) and `$id`s of embedded sub-schemas changing the base URI are not
+// supported, so schemas using them are rejected instead of being silently validated in a different way.
+use crate::{
+    comparison::json_eq,
+    error::Error,
+    fragment_helpers::fragment_from_fragment_components,
+    json_type::{get_fragment, JsonMapTrait, JsonType, PrimitiveType},
+    rust_type_impl::RustType,
+    visitor::iter_nodes,
+};
+use indexmap::IndexMap;
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+    hash::BuildHasher,
+    iter::once,
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Draft {
+    Draft7,
+    Draft202012,
+}
+
+impl Draft {
+    // Draft identified by the `$schema` URI, None if the meta-schema is not recognised
+    fn from_meta_schema(uri: &str) -> Option<Self> {
+        if uri.contains("draft-07") {
+            Some(Self::Draft7)
+        } else if uri.contains("2020-12") {
+            Some(Self::Draft202012)
+        } else {
+            None
+        }
+    }
+}
+
+// Provider of the documents referenced by `$ref`s that are not local to the schema (ie. `common.json#/$defs/id`)
+pub trait SchemaResolver {
+    // Return the document identified by `uri` (fragment excluded), None if the document is not known
+    fn resolve(&self, uri: &str) -> Option<RustType>;
+}
+
+impl<S: BuildHasher> SchemaResolver for HashMap<String, RustType, S> {
+    fn resolve(&self, uri: &str) -> Option<RustType> {
+        self.get(uri).cloned()
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct CompileOptions<'r> {
+    draft: Option<Draft>,
+    resolver: Option<&'r dyn SchemaResolver>,
+}
+
+impl fmt::Debug for CompileOptions<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("CompileOptions")
+            .field("draft", &self.draft)
+            .field("resolver", &self.resolver.map(|_| "SchemaResolver"))
+            .finish()
+    }
+}
+
+impl<'r> CompileOptions<'r> {
+    // Force the draft, by default it is detected via `$schema` (falling back to Draft 2020-12 if `$schema` is missing)
+    #[must_use]
+    pub const fn with_draft(mut self, draft: Draft) -> Self {
+        self.draft = Some(draft);
+        self
+    }
+
+    // Resolver of external references, without it only references local to the schema are allowed
+    #[must_use]
+    pub const fn with_resolver(mut self, resolver: &'r dyn SchemaResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("`{instance_pointer}` is not valid according to `{schema_pointer}`: {message}")]
+pub struct ValidationError {
+    // JSON Pointer of the invalid value within the instance
+    pub instance_pointer: String,
+    // JSON Pointer of the failing keyword within the schema.
+    // Keywords of external documents are prefixed by the document URI (ie. `common.json#/$defs/id/type`)
+    pub schema_pointer: String,
+    pub keyword: &'static str,
+    pub message: String,
+}
+
+#[derive(Clone, Debug)]
+enum Keyword {
+    Ref(usize),
+    Type(Vec<PrimitiveType>),
+    Enum(Vec<RustType>),
+    Const(RustType),
+    MultipleOf(f64),
+    Maximum(f64),
+    ExclusiveMaximum(f64),
+    Minimum(f64),
+    ExclusiveMinimum(f64),
+    MaxLength(usize),
+    MinLength(usize),
+    Pattern(Regex),
+    PrefixItems(Vec<usize>),
+    // Schema of the items following the first `prefix_length` ones
+    Items {
+        node: usize,
+        prefix_length: usize,
+    },
+    Contains {
+        node: usize,
+        min: usize,
+        max: Option<usize>,
+    },
+    MaxItems(usize),
+    MinItems(usize),
+    UniqueItems,
+    Properties(Vec<(String, usize)>),
+    PatternProperties(Vec<(Regex, usize)>),
+    // Schema of the properties not matched by the sibling `properties` and `patternProperties`
+    AdditionalProperties {
+        node: usize,
+        properties: Vec<String>,
+        patterns: Vec<Regex>,
+    },
+    PropertyNames(usize),
+    MaxProperties(usize),
+    MinProperties(usize),
+    Required(Vec<String>),
+    // Draft 7 `dependencies` covers both `dependentRequired` and `dependentSchemas`
+    Dependencies {
+        required: Vec<(String, Vec<String>)>,
+        schemas: Vec<(String, usize)>,
+    },
+    AllOf(Vec<usize>),
+    AnyOf(Vec<usize>),
+    OneOf(Vec<usize>),
+    Not(usize),
+    IfThenElse {
+        if_node: usize,
+        then_node: Option<usize>,
+        else_node: Option<usize>,
+    },
+    UnevaluatedItems(usize),
+    UnevaluatedProperties(usize),
+}
+
+#[derive(Clone, Debug)]
+struct CompiledKeyword {
+    name: &'static str,
+    location: String,
+    keyword: Keyword,
+}
+
+#[derive(Clone, Debug)]
+enum SchemaNode {
+    Boolean { location: String, value: bool },
+    Keywords { location: String, keywords: Vec<CompiledKeyword> },
+}
+
+#[derive(Clone, Debug)]
+pub struct JsonSchema {
+    draft: Draft,
+    // The root schema is the first node
+    nodes: Vec<SchemaNode>,
+}
+
+impl JsonSchema {
+    /// Compile `schema` detecting the draft via `$schema`, only references local to the schema are allowed
+    ///
+    /// # Errors
+    /// `Error::InvalidSchema` is returned if `schema` is not a valid schema or if a reference cannot be resolved
+    pub fn compile<T: JsonType>(schema: &T) -> Result<Self, Error> {
+        Self::compile_with_options(schema, CompileOptions::default())
+    }
+
+    /// # Errors
+    /// `Error::InvalidSchema` is returned if `schema` is not a valid schema, if its `$schema` is not a supported
+    /// meta-schema, if it uses unsupported keywords or if a reference cannot be resolved.
+    /// `Error::UnrecognizedValue` is returned if `schema` contains values not recognized as any JSON primitive type.
+    pub fn compile_with_options<T: JsonType>(schema: &T, options: CompileOptions) -> Result<Self, Error> {
+        let value = schema.try_to_rust_type()?;
+        let draft = match (options.draft, value.get_attribute("$schema")) {
+            (Some(draft), _) => draft,
+            (None, None) => Draft::Draft202012,
+            (None, Some(meta_schema)) => {
+                let uri = parse_string(meta_schema).map_err(|reason| Error::InvalidSchema {
+                    pointer: "/$schema".to_string(),
+                    reason,
+                })?;
+                Draft::from_meta_schema(uri).ok_or_else(|| Error::InvalidSchema {
+                    pointer: "/$schema".to_string(),
+                    reason: format!("`{uri}` is not a supported meta-schema"),
+                })?
+            }
+        };
+        let uri = value
+            .get_attribute("$id")
+            .and_then(JsonType::as_string)
+            .map_or_else(String::new, |id| id.trim_end_matches('#').to_string());
+        let mut compiler = Compiler {
+            draft,
+            resolver: options.resolver,
+            documents: vec![Document { uri, value }],
+            nodes: Vec::new(),
+            compiled_nodes: HashMap::new(),
+        };
+        let _ = compiler.compile_node(0, String::new())?;
+        Ok(Self { draft, nodes: compiler.nodes })
+    }
+
+    #[must_use]
+    pub const fn draft(&self) -> Draft {
+        self.draft
+    }
+
+    /// Validate `instance` against the schema
+    ///
+    /// # Errors
+    /// All the validation errors are returned, errors of sub-schemas used by `anyOf`, `oneOf`, `not` and `if`
+    /// are summarised by a single error on the applicator keyword.
+    pub fn validate<T: JsonType>(&self, instance: &T) -> Result<(), Vec<ValidationError>> {
+        Validator {
+            nodes: &self.nodes,
+            active_references: HashSet::new(),
+        }
+        .evaluate(0, instance, "")
+        .map(|_| ())
+    }
+
+    #[must_use]
+    pub fn is_valid<T: JsonType>(&self, instance: &T) -> bool {
+        self.validate(instance).is_ok()
+    }
+}
+
+struct Document {
+    uri: String,
+    value: RustType,
+}
+
+struct Compiler<'r> {
+    draft: Draft,
+    resolver: Option<&'r dyn SchemaResolver>,
+    documents: Vec<Document>,
+    nodes: Vec<SchemaNode>,
+    // (document index, pointer) -> node index
+    compiled_nodes: HashMap<(usize, String), usize>,
+}
+
+fn child_pointer(pointer: &str, component: &str) -> String {
+    format!("{}{}", pointer, fragment_from_fragment_components(once(component)))
+}
+
+fn invalid_value(expected: &str, value: &RustType) -> String {
+    format!("expected {}, found `{}`", expected, value.primitive_type())
+}
+
+fn parse_number(value: &RustType) -> Result<f64, String> {
+    match value {
+        #[allow(clippy::cast_precision_loss)]
+        RustType::Integer(integer) => Ok(*integer as f64),
+        RustType::Number(number) => Ok(*number),
+        _ => Err(invalid_value("a number", value)),
+    }
+}
+
+fn parse_non_negative_integer(value: &RustType) -> Result<usize, String> {
+    match value {
+        RustType::Integer(integer) => usize::try_from(*integer).map_err(|_| format!("expected a non-negative integer, found `{integer}`")),
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_sign_loss)]
+        RustType::Number(number) if *number >= 0.0 && number.fract() == 0.0 && *number <= usize::MAX as f64 => Ok(*number as usize),
+        _ => Err(invalid_value("a non-negative integer", value)),
+    }
+}
+
+fn parse_boolean(value: &RustType) -> Result<bool, String> {
+    value.as_boolean().ok_or_else(|| invalid_value("a boolean", value))
+}
+
+fn parse_string(value: &RustType) -> Result<&str, String> {
+    value.as_string().ok_or_else(|| invalid_value("a string", value))
+}
+
+fn parse_string_list(value: &RustType) -> Result<Vec<String>, String> {
+    match value {
+        RustType::List(items) => items.iter().map(|item| parse_string(item).map(ToString::to_string)).collect(),
+        _ => Err(invalid_value("an array of strings", value)),
+    }
+}
+
+fn parse_regex(value: &RustType) -> Result<Regex, String> {
+    let pattern = parse_string(value)?;
+    Regex::new(pattern).map_err(|error| format!("`{pattern}` is not a supported regular expression: {error}"))
+}
+
+fn parse_types(value: &RustType) -> Result<Vec<PrimitiveType>, String> {
+    let type_names = match value {
+        RustType::String(type_name) => vec![type_name.clone()],
+        _ => parse_string_list(value)?,
+    };
+    type_names
+        .iter()
+        .map(|type_name| PrimitiveType::try_from(type_name.as_str()).map_err(|error| error.to_string()))
+        .collect()
+}
+
+fn parse_object(value: &RustType) -> Result<&IndexMap<String, RustType>, String> {
+    match value {
+        RustType::Object(object) => Ok(object),
+        _ => Err(invalid_value("an object", value)),
+    }
+}
+
+// URI references are resolved only against the path of `base`, which covers relative file names (ie. `common.json`)
+fn join_uri(base: &str, reference: &str) -> String {
+    if reference.contains("://") {
+        reference.to_string()
+    } else {
+        base.rfind('/').map_or_else(|| reference.to_string(), |index| format!("{}{}", &base[..=index], reference))
+    }
+}
+
+// URI fragments might be percent-encoded (ie. `#/$defs/a%20b`)
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if let Some(byte) = (bytes[index] == b'%')
+            .then(|| value.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+impl Compiler<'_> {
+    fn location(&self, document: usize, pointer: &str) -> String {
+        if document == 0 {
+            pointer.to_string()
+        } else {
+            format!("{}#{}", self.documents[document].uri, pointer)
+        }
+    }
+
+    fn compile_node(&mut self, document: usize, pointer: String) -> Result<usize, Error> {
+        let key = (document, pointer);
+        if let Some(index) = self.compiled_nodes.get(&key) {
+            return Ok(*index);
+        }
+        let (document, pointer) = key;
+        let location = self.location(document, &pointer);
+        let schema = get_fragment(&self.documents[document].value, &pointer).cloned().ok_or_else(|| Error::InvalidSchema {
+            pointer: location.clone(),
+            reason: "the referenced value does not exist".to_string(),
+        })?;
+
+        // The placeholder allows recursive references to the node that is being compiled
+        let index = self.nodes.len();
+        self.nodes.push(SchemaNode::Boolean {
+            location: location.clone(),
+            value: true,
+        });
+        let _ = self.compiled_nodes.insert((document, pointer.clone()), index);
+
+        self.nodes[index] = match schema {
+            RustType::Boolean(value) => SchemaNode::Boolean { location, value },
+            RustType::Object(object) => SchemaNode::Keywords {
+                keywords: self.compile_keywords(document, &pointer, &object)?,
+                location,
+            },
+            _ => {
+                return Err(Error::InvalidSchema {
+                    pointer: location,
+                    reason: invalid_value("an object or a boolean", &schema),
+                })
+            }
+        };
+        Ok(index)
+    }
+
+    fn compile_schema_list(&mut self, document: usize, pointer: &str, value: &RustType) -> Result<Vec<usize>, Error> {
+        match value {
+            RustType::List(items) if !items.is_empty() => (0..items.len())
+                .map(|index| self.compile_node(document, child_pointer(pointer, &index.to_string())))
+                .collect(),
+            _ => Err(Error::InvalidSchema {
+                pointer: self.location(document, pointer),
+                reason: invalid_value("a non-empty array of schemas", value),
+            }),
+        }
+    }
+
+    fn compile_schema_map(&mut self, document: usize, pointer: &str, value: &RustType) -> Result<Vec<(String, usize)>, Error> {
+        let object = parse_object(value).map_err(|reason| Error::InvalidSchema {
+            pointer: self.location(document, pointer),
+            reason,
+        })?;
+        object
+            .keys()
+            .map(|key| Ok((key.clone(), self.compile_node(document, child_pointer(pointer, key))?)))
+            .collect()
+    }
+
+    fn load_document(&mut self, document: usize, uri: &str) -> Option<usize> {
+        let uri = join_uri(&self.documents[document].uri, uri);
+        if let Some(index) = self.documents.iter().position(|loaded_document| loaded_document.uri == uri) {
+            return Some(index);
+        }
+        let value = self.resolver?.resolve(&uri)?;
+        self.documents.push(Document { uri, value });
+        Some(self.documents.len() - 1)
+    }
+
+    // Pointer of the sub-schema declaring the anchor (`$anchor` for Draft 2020-12, `$id: "#name"` for Draft 7)
+    fn find_anchor(&self, document: usize, name: &str) -> Option<String> {
+        let anchor_keyword = match self.draft {
+            Draft::Draft7 => "$id",
+            Draft::Draft202012 => "$anchor",
+        };
+        iter_nodes(&self.documents[document].value).find_map(|(pointer, node)| {
+            let anchor = node.get_attribute(anchor_keyword)?.as_string()?;
+            let anchor = if self.draft == Draft::Draft7 { anchor.strip_prefix('#')? } else { anchor };
+            if anchor == name {
+                Some(pointer)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn compile_reference(&mut self, document: usize, location: &str, reference: &str) -> Result<usize, Error> {
+        let unresolvable = |reason: &str| Error::InvalidSchema {
+            pointer: location.to_string(),
+            reason: format!("unable to resolve `{reference}`: {reason}"),
+        };
+        let (uri, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let target_document = if uri.is_empty() || join_uri(&self.documents[document].uri, uri) == self.documents[document].uri {
+            document
+        } else {
+            self.load_document(document, uri).ok_or_else(|| unresolvable("the document is unknown"))?
+        };
+        let fragment = percent_decode(fragment);
+        let pointer = if fragment.is_empty() || fragment.starts_with('/') {
+            fragment
+        } else {
+            self.find_anchor(target_document, &fragment).ok_or_else(|| unresolvable("the anchor is not defined"))?
+        };
+        if get_fragment(&self.documents[target_document].value, &pointer).is_none() {
+            return Err(unresolvable("the referenced value does not exist"));
+        }
+        self.compile_node(target_document, pointer)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn compile_keywords(&mut self, document: usize, pointer: &str, object: &IndexMap<String, RustType>) -> Result<Vec<CompiledKeyword>, Error> {
+        let draft = self.draft;
+        let mut keywords = Vec::new();
+        // unevaluated* keywords depend on the annotations of all the other keywords, so they are evaluated last
+        let mut unevaluated_keywords = Vec::new();
+
+        // Before Draft 2019-09 `$ref` overrides all the sibling keywords
+        let names = if draft == Draft::Draft7 && object.contains_key("$ref") {
+            vec!["$ref"]
+        } else {
+            object.keys().map(String::as_str).collect()
+        };
+        for name in names {
+            let value = &object[name];
+            let keyword_pointer = child_pointer(pointer, name);
+            let location = self.location(document, &keyword_pointer);
+            let invalid = |reason: String| Error::InvalidSchema {
+                pointer: location.clone(),
+                reason,
+            };
+
+            let (name, keyword) = match (name, draft) {
+                ("$dynamicRef" | "$dynamicAnchor" | "$recursiveRef" | "$recursiveAnchor", _) => {
+                    return Err(invalid(format!("`{name}` is not supported")));
+                }
+                // Draft 7 `$id`s starting with `#` define anchors, see `find_anchor`
+                ("$id", _) if !pointer.is_empty() => {
+                    let id = parse_string(value).map_err(&invalid)?;
+                    let is_anchor = draft == Draft::Draft7 && id.starts_with('#');
+                    let base_uri = &self.documents[document].uri;
+                    if is_anchor || join_uri(base_uri, id.trim_end_matches('#')) == *base_uri {
+                        continue;
+                    }
+                    if draft == Draft::Draft202012 && id.trim_end_matches('#').contains('#') {
+                        return Err(invalid(format!("`{id}` contains a fragment, anchors are defined via `$anchor`")));
+                    }
+                    return Err(invalid(format!("`{id}` would change the base URI, embedded schema resources are not supported")));
+                }
+                ("$ref", _) => ("$ref", Keyword::Ref(self.compile_reference(document, &location, parse_string(value).map_err(&invalid)?)?)),
+                ("type", _) => ("type", Keyword::Type(parse_types(value).map_err(&invalid)?)),
+                ("enum", _) => (
+                    "enum",
+                    match value {
+                        RustType::List(items) => Keyword::Enum(items.clone()),
+                        _ => return Err(invalid(invalid_value("an array", value))),
+                    },
+                ),
+                ("const", _) => ("const", Keyword::Const(value.clone())),
+                ("multipleOf", _) => match parse_number(value).map_err(&invalid)? {
+                    multiple_of if multiple_of > 0.0 => ("multipleOf", Keyword::MultipleOf(multiple_of)),
+                    _ => return Err(invalid("expected a number greater than 0".to_string())),
+                },
+                ("maximum", _) => ("maximum", Keyword::Maximum(parse_number(value).map_err(&invalid)?)),
+                ("exclusiveMaximum", _) => ("exclusiveMaximum", Keyword::ExclusiveMaximum(parse_number(value).map_err(&invalid)?)),
+                ("minimum", _) => ("minimum", Keyword::Minimum(parse_number(value).map_err(&invalid)?)),
+                ("exclusiveMinimum", _) => ("exclusiveMinimum", Keyword::ExclusiveMinimum(parse_number(value).map_err(&invalid)?)),
+                ("maxLength", _) => ("maxLength", Keyword::MaxLength(parse_non_negative_integer(value).map_err(&invalid)?)),
+                ("minLength", _) => ("minLength", Keyword::MinLength(parse_non_negative_integer(value).map_err(&invalid)?)),
+                ("pattern", _) => ("pattern", Keyword::Pattern(parse_regex(value).map_err(&invalid)?)),
+                ("prefixItems", Draft::Draft202012) => ("prefixItems", Keyword::PrefixItems(self.compile_schema_list(document, &keyword_pointer, value)?)),
+                ("items", Draft::Draft7) if value.is_array() => ("items", Keyword::PrefixItems(self.compile_schema_list(document, &keyword_pointer, value)?)),
+                ("items", _) => {
+                    let prefix_length = match draft {
+                        Draft::Draft7 => 0,
+                        Draft::Draft202012 => object.get("prefixItems").and_then(JsonType::as_array).map_or(0, |items| items.len()),
+                    };
+                    let node = self.compile_node(document, keyword_pointer)?;
+                    ("items", Keyword::Items { node, prefix_length })
+                }
+                // `additionalItems` is meaningful only if `items` is an array of schemas
+                ("additionalItems", Draft::Draft7) => match object.get("items").and_then(JsonType::as_array) {
+                    Some(items) => {
+                        let prefix_length = items.len();
+                        let node = self.compile_node(document, keyword_pointer)?;
+                        ("additionalItems", Keyword::Items { node, prefix_length })
+                    }
+                    None => continue,
+                },
+                ("contains", _) => {
+                    let (min, max) = match draft {
+                        Draft::Draft7 => (1, None),
+                        Draft::Draft202012 => (
+                            object.get("minContains").map_or(Ok(1), parse_non_negative_integer).map_err(&invalid)?,
+                            object.get("maxContains").map(parse_non_negative_integer).transpose().map_err(&invalid)?,
+                        ),
+                    };
+                    let node = self.compile_node(document, keyword_pointer)?;
+                    ("contains", Keyword::Contains { node, min, max })
+                }
+                ("maxItems", _) => ("maxItems", Keyword::MaxItems(parse_non_negative_integer(value).map_err(&invalid)?)),
+                ("minItems", _) => ("minItems", Keyword::MinItems(parse_non_negative_integer(value).map_err(&invalid)?)),
+                ("uniqueItems", _) => {
+                    if parse_boolean(value).map_err(&invalid)? {
+                        ("uniqueItems", Keyword::UniqueItems)
+                    } else {
+                        continue;
+                    }
+                }
+                ("properties", _) => ("properties", Keyword::Properties(self.compile_schema_map(document, &keyword_pointer, value)?)),
+                ("patternProperties", _) => {
+                    let patterns = parse_object(value)
+                        .map_err(&invalid)?
+                        .keys()
+                        .map(|pattern| Regex::new(pattern).map_err(|error| invalid(format!("`{pattern}` is not a supported regular expression: {error}"))))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let nodes = self.compile_schema_map(document, &keyword_pointer, value)?;
+                    (
+                        "patternProperties",
+                        Keyword::PatternProperties(patterns.into_iter().zip(nodes).map(|(regex, (_, node))| (regex, node)).collect()),
+                    )
+                }
+                ("additionalProperties", _) => {
+                    let properties = object
+                        .get("properties")
+                        .and_then(JsonType::as_object)
+                        .map(|properties| properties.keys().map(ToString::to_string).collect())
+                        .unwrap_or_default();
+                    // Invalid patterns are reported while compiling `patternProperties`
+                    let patterns = object
+                        .get("patternProperties")
+                        .and_then(JsonType::as_object)
+                        .map(|pattern_properties| pattern_properties.keys().filter_map(|pattern| Regex::new(pattern).ok()).collect())
+                        .unwrap_or_default();
+                    let node = self.compile_node(document, keyword_pointer)?;
+                    ("additionalProperties", Keyword::AdditionalProperties { node, properties, patterns })
+                }
+                ("propertyNames", _) => ("propertyNames", Keyword::PropertyNames(self.compile_node(document, keyword_pointer)?)),
+                ("maxProperties", _) => ("maxProperties", Keyword::MaxProperties(parse_non_negative_integer(value).map_err(&invalid)?)),
+                ("minProperties", _) => ("minProperties", Keyword::MinProperties(parse_non_negative_integer(value).map_err(&invalid)?)),
+                ("required", _) => ("required", Keyword::Required(parse_string_list(value).map_err(&invalid)?)),
+                ("dependencies", Draft::Draft7) => {
+                    let mut required = Vec::new();
+                    let mut schemas = Vec::new();
+                    for (property, dependency) in parse_object(value).map_err(&invalid)? {
+                        if dependency.is_array() {
+                            required.push((property.clone(), parse_string_list(dependency).map_err(&invalid)?));
+                        } else {
+                            schemas.push((property.clone(), self.compile_node(document, child_pointer(&keyword_pointer, property))?));
+                        }
+                    }
+                    ("dependencies", Keyword::Dependencies { required, schemas })
+                }
+                ("dependentRequired", Draft::Draft202012) => {
+                    let required = parse_object(value)
+                        .map_err(&invalid)?
+                        .iter()
+                        .map(|(property, dependency)| Ok((property.clone(), parse_string_list(dependency).map_err(&invalid)?)))
+                        .collect::<Result<Vec<_>, Error>>()?;
+                    ("dependentRequired", Keyword::Dependencies { required, schemas: Vec::new() })
+                }
+                ("dependentSchemas", Draft::Draft202012) => {
+                    let schemas = self.compile_schema_map(document, &keyword_pointer, value)?;
+                    ("dependentSchemas", Keyword::Dependencies { required: Vec::new(), schemas })
+                }
+                ("allOf", _) => ("allOf", Keyword::AllOf(self.compile_schema_list(document, &keyword_pointer, value)?)),
+                ("anyOf", _) => ("anyOf", Keyword::AnyOf(self.compile_schema_list(document, &keyword_pointer, value)?)),
+                ("oneOf", _) => ("oneOf", Keyword::OneOf(self.compile_schema_list(document, &keyword_pointer, value)?)),
+                ("not", _) => ("not", Keyword::Not(self.compile_node(document, keyword_pointer)?)),
+                // `then` and `else` are ignored if `if` is not present
+                ("if", _) => {
+                    let if_node = self.compile_node(document, keyword_pointer)?;
+                    let then_node = if object.contains_key("then") {
+                        Some(self.compile_node(document, child_pointer(pointer, "then"))?)
+                    } else {
+                        None
+                    };
+                    let else_node = if object.contains_key("else") {
+                        Some(self.compile_node(document, child_pointer(pointer, "else"))?)
+                    } else {
+                        None
+                    };
+                    ("if", Keyword::IfThenElse { if_node, then_node, else_node })
+                }
+                ("unevaluatedItems", Draft::Draft202012) => {
+                    let node = self.compile_node(document, keyword_pointer)?;
+                    unevaluated_keywords.push(CompiledKeyword {
+                        name: "unevaluatedItems",
+                        location,
+                        keyword: Keyword::UnevaluatedItems(node),
+                    });
+                    continue;
+                }
+                ("unevaluatedProperties", Draft::Draft202012) => {
+                    let node = self.compile_node(document, keyword_pointer)?;
+                    unevaluated_keywords.push(CompiledKeyword {
+                        name: "unevaluatedProperties",
+                        location,
+                        keyword: Keyword::UnevaluatedProperties(node),
+                    });
+                    continue;
+                }
+                // Annotations, definitions and unknown keywords do not affect the validation
+                _ => continue,
+            };
+            keywords.push(CompiledKeyword { name, location, keyword });
+        }
+        keywords.extend(unevaluated_keywords);
+        Ok(keywords)
+    }
+}
+
+// Properties and items successfully evaluated by the keywords applied to an instance location.
+// They are needed by `unevaluatedProperties` and `unevaluatedItems`.
+#[derive(Default)]
+struct Annotations {
+    properties: HashSet<String>,
+    items: HashSet<usize>,
+}
+
+impl Annotations {
+    fn merge(&mut self, other: Self) {
+        self.properties.extend(other.properties);
+        self.items.extend(other.items);
+    }
+}
+
+// Type names as spelled by the `type` keyword
+fn type_name(primitive_type: PrimitiveType) -> &'static str {
+    primitive_type.into()
+}
+
+// Numbers with a zero fractional part are integers for JSON Schema (ie. `1.0`)
+fn has_type<T: JsonType>(instance: &T, instance_type: PrimitiveType, primitive_type: PrimitiveType) -> bool {
+    match (primitive_type, instance_type) {
+        (expected, actual) if expected == actual => true,
+        (PrimitiveType::Number, PrimitiveType::Integer) => true,
+        (PrimitiveType::Integer, PrimitiveType::Number) => instance.as_number().is_some_and(|number| number.is_finite() && number.fract() == 0.0),
+        _ => false,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn numeric_value<T: JsonType>(instance: &T, instance_type: PrimitiveType) -> Option<f64> {
+    match instance_type {
+        PrimitiveType::Integer => instance.as_integer().map(|integer| integer as f64),
+        PrimitiveType::Number => instance.as_number(),
+        _ => None,
+    }
+}
+
+fn is_multiple_of<T: JsonType>(instance: &T, instance_type: PrimitiveType, multiple_of: f64) -> bool {
+    #[allow(clippy::cast_possible_truncation)]
+    if let (Some(integer), true) = (instance.as_integer(), multiple_of.fract() == 0.0 && multiple_of < 2_f64.powi(127)) {
+        return integer % (multiple_of as i128) == 0;
+    }
+    numeric_value(instance, instance_type).is_none_or(|value| {
+        let quotient = value / multiple_of;
+        quotient.is_finite() && (quotient - quotient.round()).abs() <= f64::EPSILON * quotient.abs().max(1.0)
+    })
+}
+
+fn object_keys<T: JsonType>(instance: &T) -> Vec<String> {
+    instance.as_object().map(|object| object.keys().map(ToString::to_string).collect()).unwrap_or_default()
+}
+
+struct Validator<'s> {
+    nodes: &'s [SchemaNode],
+    // (node, instance pointer) of the references being evaluated.
+    // A reference evaluated again on the same instance location is a cycle that would never terminate.
+    active_references: HashSet<(usize, String)>,
+}
+
+impl Validator<'_> {
+    fn evaluate<T: JsonType>(&mut self, node: usize, instance: &T, instance_pointer: &str) -> Result<Annotations, Vec<ValidationError>> {
+        let nodes = self.nodes;
+        let (location, keywords) = match &nodes[node] {
+            SchemaNode::Boolean { value: true, .. } => return Ok(Annotations::default()),
+            SchemaNode::Boolean { location, value: false } => {
+                return Err(vec![ValidationError {
+                    instance_pointer: instance_pointer.to_string(),
+                    schema_pointer: location.clone(),
+                    keyword: "false",
+                    message: "no value is valid against the `false` schema".to_string(),
+                }])
+            }
+            SchemaNode::Keywords { location, keywords } => (location, keywords),
+        };
+        // Values not recognized as any JSON primitive type (ie. from dynamically typed backends) cannot be validated
+        let instance_type = instance.try_primitive_type().map_err(|error| {
+            vec![ValidationError {
+                instance_pointer: instance_pointer.to_string(),
+                schema_pointer: location.clone(),
+                keyword: "type",
+                message: error.to_string(),
+            }]
+        })?;
+        let mut annotations = Annotations::default();
+        let mut errors = Vec::new();
+        for keyword in keywords {
+            self.evaluate_keyword(keyword, instance, instance_type, instance_pointer, &mut annotations, &mut errors);
+        }
+        if errors.is_empty() {
+            Ok(annotations)
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Evaluate `node` on the same instance location, merging its annotations if the evaluation succeeds
+    fn apply<T: JsonType>(&mut self, node: usize, instance: &T, instance_pointer: &str, annotations: &mut Annotations, errors: &mut Vec<ValidationError>) {
+        match self.evaluate(node, instance, instance_pointer) {
+            Ok(node_annotations) => annotations.merge(node_annotations),
+            Err(node_errors) => errors.extend(node_errors),
+        }
+    }
+
+    // Evaluate `node` on a child of the instance, returns true if the child is valid
+    fn apply_to_child<T: JsonType>(&mut self, node: usize, child: &T, child_pointer: &str, errors: &mut Vec<ValidationError>) -> bool {
+        match self.evaluate(node, child, child_pointer) {
+            Ok(_) => true,
+            Err(child_errors) => {
+                errors.extend(child_errors);
+                false
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn evaluate_keyword<T: JsonType>(
+        &mut self,
+        compiled_keyword: &CompiledKeyword,
+        instance: &T,
+        instance_type: PrimitiveType,
+        instance_pointer: &str,
+        annotations: &mut Annotations,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let error = |message: String| ValidationError {
+            instance_pointer: instance_pointer.to_string(),
+            schema_pointer: compiled_keyword.location.clone(),
+            keyword: compiled_keyword.name,
+            message,
+        };
+        let items = || instance.as_array().map(Iterator::collect::<Vec<_>>).unwrap_or_default();
+
+        match &compiled_keyword.keyword {
+            Keyword::Ref(node) => {
+                let reference = (*node, instance_pointer.to_string());
+                if self.active_references.insert(reference.clone()) {
+                    self.apply(*node, instance, instance_pointer, annotations, errors);
+                    let _ = self.active_references.remove(&reference);
+                }
+            }
+            Keyword::Type(types) => {
+                if !types.iter().any(|primitive_type| has_type(instance, instance_type, *primitive_type)) {
+                    let type_names = types.iter().map(|primitive_type| format!("`{}`", type_name(*primitive_type))).collect::<Vec<_>>();
+                    errors.push(error(format!("expected a value of type {}, found `{}`", type_names.join(" or "), type_name(instance_type))));
+                }
+            }
+            Keyword::Enum(values) => {
+                if !values.iter().any(|value| json_eq(instance, value)) {
+                    errors.push(error("the value is not one of the allowed values".to_string()));
+                }
+            }
+            Keyword::Const(value) => {
+                if !json_eq(instance, value) {
+                    errors.push(error("the value is different from the expected constant".to_string()));
+                }
+            }
+            Keyword::MultipleOf(multiple_of) => {
+                if !is_multiple_of(instance, instance_type, *multiple_of) {
+                    errors.push(error(format!("the value is not a multiple of `{multiple_of}`")));
+                }
+            }
+            Keyword::Maximum(limit) | Keyword::ExclusiveMaximum(limit) | Keyword::Minimum(limit) | Keyword::ExclusiveMinimum(limit) => {
+                if let Some(value) = numeric_value(instance, instance_type) {
+                    let (is_valid, relation) = match &compiled_keyword.keyword {
+                        Keyword::Maximum(_) => (value <= *limit, "less than or equal to"),
+                        Keyword::ExclusiveMaximum(_) => (value < *limit, "less than"),
+                        Keyword::Minimum(_) => (value >= *limit, "greater than or equal to"),
+                        _ => (value > *limit, "greater than"),
+                    };
+                    if !is_valid {
+                        errors.push(error(format!("`{value}` is not {relation} `{limit}`")));
+                    }
+                }
+            }
+            Keyword::MaxLength(limit) | Keyword::MinLength(limit) => {
+                if let Some(length) = instance.as_string().map(|string| string.chars().count()) {
+                    match &compiled_keyword.keyword {
+                        Keyword::MaxLength(_) if length > *limit => errors.push(error(format!("the string is longer than {limit} characters"))),
+                        Keyword::MinLength(_) if length < *limit => errors.push(error(format!("the string is shorter than {limit} characters"))),
+                        _ => {}
+                    }
+                }
+            }
+            Keyword::Pattern(regex) => {
+                if let Some(string) = instance.as_string() {
+                    if !regex.is_match(string) {
+                        errors.push(error(format!("the string does not match `{regex}`")));
+                    }
+                }
+            }
+            Keyword::PrefixItems(nodes) => {
+                for (index, (item, node)) in items().into_iter().zip(nodes).enumerate() {
+                    let _ = self.apply_to_child(*node, item, &child_pointer(instance_pointer, &index.to_string()), errors);
+                    let _ = annotations.items.insert(index);
+                }
+            }
+            Keyword::Items { node, prefix_length } => {
+                for (index, item) in items().into_iter().enumerate().skip(*prefix_length) {
+                    let _ = self.apply_to_child(*node, item, &child_pointer(instance_pointer, &index.to_string()), errors);
+                    let _ = annotations.items.insert(index);
+                }
+            }
+            Keyword::Contains { node, min, max } => {
+                if instance.is_array() {
+                    let mut matches = 0;
+                    for (index, item) in items().into_iter().enumerate() {
+                        if self.apply_to_child(*node, item, &child_pointer(instance_pointer, &index.to_string()), &mut Vec::new()) {
+                            matches += 1;
+                            let _ = annotations.items.insert(index);
+                        }
+                    }
+                    if matches < *min {
+                        errors.push(error(format!("the array contains {matches} matching items, expected at least {min}")));
+                    } else if max.is_some_and(|max| matches > max) {
+                        errors.push(error(format!(
+                            "the array contains {} matching items, expected at most {}",
+                            matches,
+                            max.unwrap_or_default()
+                        )));
+                    }
+                }
+            }
+            Keyword::MaxItems(limit) | Keyword::MinItems(limit) => {
+                if let Some(length) = instance.as_array().map(|array| array.len()) {
+                    match &compiled_keyword.keyword {
+                        Keyword::MaxItems(_) if length > *limit => errors.push(error(format!("the array has more than {limit} items"))),
+                        Keyword::MinItems(_) if length < *limit => errors.push(error(format!("the array has less than {limit} items"))),
+                        _ => {}
+                    }
+                }
+            }
+            Keyword::UniqueItems => {
+                let items = items();
+                let duplicate = (0..items.len()).find_map(|index| {
+                    ((index + 1)..items.len())
+                        .find(|other_index| json_eq(items[index], items[*other_index]))
+                        .map(|other_index| (index, other_index))
+                });
+                if let Some((index, other_index)) = duplicate {
+                    errors.push(error(format!("items #{index} and #{other_index} are equal")));
+                }
+            }
+            Keyword::Properties(properties) => {
+                for (property, node) in properties {
+                    if let Some(value) = instance.get_attribute(property) {
+                        let _ = self.apply_to_child(*node, value, &child_pointer(instance_pointer, property), errors);
+                        let _ = annotations.properties.insert(property.clone());
+                    }
+                }
+            }
+            Keyword::PatternProperties(patterns) => {
+                for key in object_keys(instance) {
+                    for (regex, node) in patterns {
+                        if let (true, Some(value)) = (regex.is_match(&key), instance.get_attribute(&key)) {
+                            let _ = self.apply_to_child(*node, value, &child_pointer(instance_pointer, &key), errors);
+                            let _ = annotations.properties.insert(key.clone());
+                        }
+                    }
+                }
+            }
+            Keyword::AdditionalProperties { node, properties, patterns } => {
+                for key in object_keys(instance) {
+                    if properties.contains(&key) || patterns.iter().any(|regex| regex.is_match(&key)) {
+                        continue;
+                    }
+                    if let Some(value) = instance.get_attribute(&key) {
+                        let _ = self.apply_to_child(*node, value, &child_pointer(instance_pointer, &key), errors);
+                        let _ = annotations.properties.insert(key);
+                    }
+                }
+            }
+            Keyword::PropertyNames(node) => {
+                for key in object_keys(instance) {
+                    let pointer = child_pointer(instance_pointer, &key);
+                    let _ = self.apply_to_child(*node, &RustType::String(key), &pointer, errors);
+                }
+            }
+            Keyword::MaxProperties(limit) | Keyword::MinProperties(limit) => {
+                if instance.is_object() {
+                    let length = object_keys(instance).len();
+                    match &compiled_keyword.keyword {
+                        Keyword::MaxProperties(_) if length > *limit => errors.push(error(format!("the object has more than {limit} properties"))),
+                        Keyword::MinProperties(_) if length < *limit => errors.push(error(format!("the object has less than {limit} properties"))),
+                        _ => {}
+                    }
+                }
+            }
+            Keyword::Required(properties) => {
+                if instance.is_object() {
+                    let missing = properties
+                        .iter()
+                        .filter(|property| !instance.has_attribute(property))
+                        .map(|property| format!("`{property}`"))
+                        .collect::<Vec<_>>();
+                    if !missing.is_empty() {
+                        errors.push(error(format!("missing required properties: {}", missing.join(", "))));
+                    }
+                }
+            }
+            Keyword::Dependencies { required, schemas } => {
+                if instance.is_object() {
+                    for (property, dependencies) in required {
+                        if instance.has_attribute(property) {
+                            let missing = dependencies
+                                .iter()
+                                .filter(|dependency| !instance.has_attribute(dependency))
+                                .map(|dependency| format!("`{dependency}`"))
+                                .collect::<Vec<_>>();
+                            if !missing.is_empty() {
+                                errors.push(error(format!("`{}` requires the properties {}", property, missing.join(", "))));
+                            }
+                        }
+                    }
+                    for (property, node) in schemas {
+                        if instance.has_attribute(property) {
+                            self.apply(*node, instance, instance_pointer, annotations, errors);
+                        }
+                    }
+                }
+            }
+            Keyword::AllOf(nodes) => {
+                for node in nodes {
+                    self.apply(*node, instance, instance_pointer, annotations, errors);
+                }
+            }
+            Keyword::AnyOf(nodes) => {
+                let mut is_valid = false;
+                // All the sub-schemas are evaluated as their annotations are needed by the unevaluated* keywords
+                for node in nodes {
+                    if let Ok(node_annotations) = self.evaluate(*node, instance, instance_pointer) {
+                        annotations.merge(node_annotations);
+                        is_valid = true;
+                    }
+                }
+                if !is_valid {
+                    errors.push(error("the value is not valid against any of the schemas".to_string()));
+                }
+            }
+            Keyword::OneOf(nodes) => {
+                let mut valid_nodes = Vec::new();
+                for (index, node) in nodes.iter().enumerate() {
+                    if let Ok(node_annotations) = self.evaluate(*node, instance, instance_pointer) {
+                        valid_nodes.push((index, node_annotations));
+                    }
+                }
+                match valid_nodes.len() {
+                    0 => errors.push(error("the value is not valid against any of the schemas".to_string())),
+                    1 => annotations.merge(valid_nodes.remove(0).1),
+                    _ => errors.push(error(format!(
+                        "the value is valid against more than one schema (#{} and #{})",
+                        valid_nodes[0].0, valid_nodes[1].0
+                    ))),
+                }
+            }
+            Keyword::Not(node) => {
+                if self.evaluate(*node, instance, instance_pointer).is_ok() {
+                    errors.push(error("the value must not be valid against the schema".to_string()));
+                }
+            }
+            Keyword::IfThenElse { if_node, then_node, else_node } => match self.evaluate(*if_node, instance, instance_pointer) {
+                Ok(if_annotations) => {
+                    annotations.merge(if_annotations);
+                    if let Some(then_node) = then_node {
+                        self.apply(*then_node, instance, instance_pointer, annotations, errors);
+                    }
+                }
+                Err(_) => {
+                    if let Some(else_node) = else_node {
+                        self.apply(*else_node, instance, instance_pointer, annotations, errors);
+                    }
+                }
+            },
+            Keyword::UnevaluatedItems(node) => {
+                for (index, item) in items().into_iter().enumerate() {
+                    if annotations.items.insert(index) {
+                        let _ = self.apply_to_child(*node, item, &child_pointer(instance_pointer, &index.to_string()), errors);
+                    }
+                }
+            }
+            Keyword::UnevaluatedProperties(node) => {
+                for key in object_keys(instance) {
+                    if let (false, Some(value)) = (annotations.properties.contains(&key), instance.get_attribute(&key)) {
+                        let _ = self.apply_to_child(*node, value, &child_pointer(instance_pointer, &key), errors);
+                        let _ = annotations.properties.insert(key);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompileOptions, Draft, JsonSchema, ValidationError};
+    use crate::{error::Error, rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use std::collections::HashMap;
+    use test_case::test_case;
+
+    fn errors(schema: &RustType, instance: &RustType) -> Vec<(String, String, &'static str)> {
+        JsonSchema::compile(schema)
+            .unwrap()
+            .validate(instance)
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(
+                |ValidationError {
+                     instance_pointer,
+                     schema_pointer,
+                     keyword,
+                     ..
+                 }| (instance_pointer, schema_pointer, keyword),
+            )
+            .collect()
+    }
+
+    #[test_case(&rust_type!({"type": "integer"}), &rust_type!(1.0) => true ; "integer with zero fraction")]
+    #[test_case(&rust_type!({"type": "integer"}), &rust_type!(1.5) => false ; "integer with fraction")]
+    #[test_case(&rust_type!({"type": ["number", "null"]}), &rust_type!(null) => true ; "type list")]
+    #[test_case(&rust_type!({"enum": [1, "a"]}), &rust_type!(1.0) => true ; "enum keyword")]
+    #[test_case(&rust_type!({"const": {"a": [1]}}), &rust_type!({"a": [2]}) => false ; "const keyword")]
+    #[test_case(&rust_type!({"multipleOf": 0.1}), &rust_type!(0.3) => true ; "float multiple of")]
+    #[test_case(&rust_type!({"multipleOf": 3}), &rust_type!(10) => false ; "integer multiple of")]
+    #[test_case(&rust_type!({"exclusiveMinimum": 1, "maximum": 2}), &rust_type!(1) => false ; "exclusive minimum")]
+    #[test_case(&rust_type!({"minLength": 2, "maxLength": 2}), &rust_type!("àè") => true ; "length in characters")]
+    #[test_case(&rust_type!({"pattern": "^[a-z]+$"}), &rust_type!("a1") => false ; "pattern")]
+    #[test_case(&rust_type!({"pattern": "^[a-z]+$"}), &rust_type!(1) => true ; "pattern ignores other types")]
+    #[test_case(&rust_type!({"uniqueItems": true}), &rust_type!([1, "1", 1.0]) => false ; "unique items")]
+    #[test_case(&rust_type!({"contains": {"type": "string"}, "minContains": 2}), &rust_type!(["a", 1, "b"]) => true ; "min contains")]
+    #[test_case(&rust_type!({"contains": {"type": "string"}, "maxContains": 1}), &rust_type!(["a", 1, "b"]) => false ; "max contains")]
+    #[test_case(&rust_type!({"propertyNames": {"maxLength": 1}}), &rust_type!({"a": 1, "bc": 2}) => false ; "property names")]
+    #[test_case(&rust_type!({"dependentRequired": {"a": ["b"]}}), &rust_type!({"a": 1}) => false ; "dependent required")]
+    #[test_case(&rust_type!({"oneOf": [{"type": "integer"}, {"type": "number"}]}), &rust_type!(1) => false ; "one of matching two schemas")]
+    #[test_case(&rust_type!({"anyOf": [{"type": "integer"}, {"type": "string"}]}), &rust_type!("a") => true ; "any of")]
+    #[test_case(&rust_type!({"not": {"type": "null"}}), &rust_type!(null) => false ; "not keyword")]
+    #[test_case(&rust_type!({"if": {"minimum": 10}, "then": {"multipleOf": 10}, "else": {"maximum": 5}}), &rust_type!(7) => false ; "if then else")]
+    #[test_case(&rust_type!(false), &rust_type!(null) => false ; "false schema")]
+    fn test_is_valid(schema: &RustType, instance: &RustType) -> bool {
+        JsonSchema::compile(schema).unwrap().is_valid(instance)
+    }
+
+    #[test]
+    fn test_error_pointers() {
+        let schema = rust_type!({
+            "type": "object",
+            "properties": {"items": {"type": "array", "items": {"$ref": "#/$defs/item"}}},
+            "required": ["name"],
+            "$defs": {"item": {"type": "object", "properties": {"id": {"type": "integer"}}}},
+        });
+        assert_eq!(
+            errors(&schema, &rust_type!({"items": [{"id": 1}, {"id": "2"}]})),
+            vec![
+                ("/items/1/id".to_string(), "/$defs/item/properties/id/type".to_string(), "type"),
+                (String::new(), "/required".to_string(), "required"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_draft_7_items_and_ref_overriding_siblings() {
+        let schema = rust_type!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "definitions": {"positive": {"exclusiveMinimum": 0}},
+            "items": [{"type": "string"}, {"$ref": "#/definitions/positive", "type": "string"}],
+            "additionalItems": false,
+        });
+        let compiled = JsonSchema::compile(&schema).unwrap();
+        assert_eq!(compiled.draft(), Draft::Draft7);
+        assert!(compiled.is_valid(&rust_type!(["a", 1])));
+        assert_eq!(errors(&schema, &rust_type!(["a", 1, 2])), vec![("/2".to_string(), "/additionalItems".to_string(), "false")]);
+    }
+
+    #[test]
+    fn test_draft_2020_12_prefix_items() {
+        let schema = rust_type!({"prefixItems": [{"type": "string"}], "items": {"type": "integer"}});
+        assert_eq!(errors(&schema, &rust_type!(["a", 1, "b"])), vec![("/2".to_string(), "/items/type".to_string(), "type")]);
+    }
+
+    #[test]
+    fn test_recursive_schema() {
+        let schema = rust_type!({
+            "$defs": {"node": {"type": "object", "properties": {"children": {"type": "array", "items": {"$ref": "#/$defs/node"}}}}},
+            "$ref": "#/$defs/node",
+        });
+        assert!(JsonSchema::compile(&schema).unwrap().is_valid(&rust_type!({"children": [{"children": []}]})));
+        assert!(!JsonSchema::compile(&schema).unwrap().is_valid(&rust_type!({"children": [{"children": 1}]})));
+        // A reference cycle that does not consume the instance must terminate
+        assert!(JsonSchema::compile(&rust_type!({"$ref": "#"})).unwrap().is_valid(&rust_type!(1)));
+    }
+
+    #[test]
+    fn test_anchor_and_percent_encoded_references() {
+        let schema = rust_type!({
+            "$defs": {"a b": {"$anchor": "name", "type": "string"}},
+            "properties": {"x": {"$ref": "#name"}, "y": {"$ref": "#/$defs/a%20b"}},
+        });
+        assert_eq!(
+            errors(&schema, &rust_type!({"x": 1, "y": 2})),
+            vec![
+                ("/x".to_string(), "/$defs/a b/type".to_string(), "type"),
+                ("/y".to_string(), "/$defs/a b/type".to_string(), "type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unevaluated_properties() {
+        let schema = rust_type!({
+            "allOf": [{"properties": {"a": true}}],
+            "anyOf": [{"properties": {"b": true}, "required": ["b"]}, {"properties": {"c": true}, "required": ["d"]}],
+            "unevaluatedProperties": false,
+        });
+        assert!(JsonSchema::compile(&schema).unwrap().is_valid(&rust_type!({"a": 1, "b": 2})));
+        // `c` is not evaluated as the second `anyOf` branch fails
+        assert_eq!(
+            errors(&schema, &rust_type!({"a": 1, "b": 2, "c": 3})),
+            vec![("/c".to_string(), "/unevaluatedProperties".to_string(), "false")]
+        );
+    }
+
+    #[test]
+    fn test_external_references() {
+        let mut documents = HashMap::new();
+        let _ = documents.insert("http://example.com/common.json".to_string(), rust_type!({"$defs": {"id": {"type": "integer"}}}));
+        let schema = rust_type!({"$id": "http://example.com/root.json", "items": {"$ref": "common.json#/$defs/id"}});
+
+        let compiled = JsonSchema::compile_with_options(&schema, CompileOptions::default().with_resolver(&documents)).unwrap();
+        let validation_errors = compiled.validate(&rust_type!([1, "2"])).unwrap_err();
+        assert_eq!(validation_errors.len(), 1);
+        assert_eq!(validation_errors[0].instance_pointer, "/1");
+        assert_eq!(validation_errors[0].schema_pointer, "http://example.com/common.json#/$defs/id/type");
+
+        assert_eq!(
+            JsonSchema::compile(&schema).unwrap_err(),
+            Error::InvalidSchema {
+                pointer: "/items/$ref".to_string(),
+                reason: "unable to resolve `common.json#/$defs/id`: the document is unknown".to_string(),
+            }
+        );
+    }
+
+    #[test_case(&rust_type!(1), "" ; "not a schema")]
+    #[test_case(&rust_type!({"minLength": -1}), "/minLength" ; "negative length")]
+    #[test_case(&rust_type!({"type": "float"}), "/type" ; "unknown type")]
+    #[test_case(&rust_type!({"properties": {"a": {"pattern": "("}}}), "/properties/a/pattern" ; "invalid regex")]
+    #[test_case(&rust_type!({"$ref": "#/$defs/missing"}), "/$ref" ; "missing reference")]
+    #[test_case(&rust_type!({"$schema": "http://json-schema.org/draft-04/schema#"}), "/$schema" ; "unsupported meta-schema")]
+    #[test_case(&rust_type!({"$schema": 7}), "/$schema" ; "invalid meta-schema")]
+    #[test_case(&rust_type!({"$dynamicAnchor": "node", "items": {"$dynamicRef": "#node"}}), "/$dynamicAnchor" ; "dynamic anchor")]
+    #[test_case(&rust_type!({"items": {"$dynamicRef": "#node"}}), "/items/$dynamicRef" ; "dynamic reference")]
+    #[test_case(&rust_type!({"$schema": "http://json-schema.org/draft-07/schema#", "items": {"$recursiveRef": "#"}}), "/items/$recursiveRef" ; "recursive reference")]
+    #[test_case(&rust_type!({"$id": "http://example.com/root.json", "items": {"$id": "item.json"}}), "/items/$id" ; "embedded schema resource")]
+    #[test_case(&rust_type!({"items": {"$id": "#item"}}), "/items/$id" ; "draft 2020-12 anchor as id")]
+    fn test_invalid_schema(schema: &RustType, expected_pointer: &str) {
+        match JsonSchema::compile(schema) {
+            Err(Error::InvalidSchema { pointer, .. }) => assert_eq!(pointer, expected_pointer),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_embedded_ids_not_changing_the_base_uri() {
+        let schema = rust_type!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "$id": "http://example.com/root.json",
+            "definitions": {"a": {"$id": "#a", "type": "integer"}},
+            "properties": {"a": {"$ref": "#a"}, "b": {"$id": "http://example.com/root.json#", "type": "string"}},
+        });
+        let compiled = JsonSchema::compile(&schema).unwrap();
+        assert!(compiled.is_valid(&rust_type!({"a": 1, "b": "c"})));
+        assert!(!compiled.is_valid(&rust_type!({"a": "1"})));
+    }
+
+    #[test]
+    fn test_unrecognized_values() {
+        let schema = JsonSchema::compile(&rust_type!({"items": {"type": "integer"}, "uniqueItems": true})).unwrap();
+        let instance = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        let errors = schema.validate(&instance).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.instance_pointer.as_str(), error.schema_pointer.as_str(), error.keyword))
+                .collect::<Vec<_>>(),
+            vec![("/1", "/items", "type")]
+        );
+        assert!(schema.is_valid(&PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1)])));
+        assert!(JsonSchema::compile(&rust_type!(true)).unwrap().is_valid(&PartiallyRecognizedValue::Unrecognized));
+        assert_eq!(
+            JsonSchema::compile(&PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Unrecognized])).unwrap_err(),
+            Error::UnrecognizedValue
+        );
+    }
+
+    #[test]
+    fn test_forced_draft() {
+        // `dependencies` is not a Draft 2020-12 keyword
+        let schema = rust_type!({"dependencies": {"a": ["b"]}});
+        assert!(JsonSchema::compile(&schema).unwrap().is_valid(&rust_type!({"a": 1})));
+        assert!(!JsonSchema::compile_with_options(&schema, CompileOptions::default().with_draft(Draft::Draft7))
+            .unwrap()
+            .is_valid(&rust_type!({"a": 1})));
+    }
+}
+
+#[cfg(all(test, feature = "trait_serde_json"))]
+mod tests_serde_json {
+    use super::JsonSchema;
+
+    #[test]
+    fn test_validate_serde_json_instance_with_serde_json_schema() {
+        let schema = JsonSchema::compile(&json!({"type": "object", "additionalProperties": {"type": "string"}})).unwrap();
+        assert!(schema.is_valid(&json!({"a": "b"})));
+        let errors = schema.validate(&json!({"a": "b", "c": 1})).unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "`/c` is not valid according to `/additionalProperties/type`: expected a value of type `string`, found `integer`"
+        );
+    }
+}
@@ -0,0 +1,330 @@
+// Inference of a Draft 2020-12 JSON Schema from sample documents.
+// The samples are walked once, collecting per path the observed primitive types, the properties frequency,
+// the distinct string values (up to a limit) and the shape of the array items.
+use crate::{
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+    rust_type_impl::RustType,
+};
+use indexmap::{IndexMap, IndexSet};
+
+const DRAFT_2020_12_META_SCHEMA: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InferenceOptions {
+    max_enum_values: usize,
+    required_threshold: f64,
+}
+
+impl Default for InferenceOptions {
+    fn default() -> Self {
+        Self {
+            max_enum_values: 5,
+            required_threshold: 1.0,
+        }
+    }
+}
+
+impl InferenceOptions {
+    // Maximum number of distinct values of a string field for it to be reported as `enum`, 0 disables enums.
+    // An `enum` is emitted only if, on average, each distinct value has been observed at least twice.
+    #[must_use]
+    pub const fn with_max_enum_values(mut self, max_enum_values: usize) -> Self {
+        self.max_enum_values = max_enum_values;
+        self
+    }
+
+    // Minimum fraction (0.0 to 1.0) of the objects containing a property for it to be reported as `required`
+    #[must_use]
+    pub const fn with_required_threshold(mut self, required_threshold: f64) -> Self {
+        self.required_threshold = required_threshold;
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct StringShape {
+    count: usize,
+    // Distinct values, collection stops once more than `max_enum_values` values are observed
+    values: IndexSet<String>,
+    has_too_many_values: bool,
+}
+
+#[derive(Debug, Default)]
+struct ArrayShape {
+    count: usize,
+    items: Shape,
+}
+
+#[derive(Debug, Default)]
+struct ObjectShape {
+    count: usize,
+    // Number of objects containing each property is stored in the `count` of the property shape
+    properties: IndexMap<String, Shape>,
+}
+
+// Summary of all the values observed at a given path
+#[derive(Debug, Default)]
+struct Shape {
+    count: usize,
+    nulls: usize,
+    booleans: usize,
+    integers: usize,
+    numbers: usize,
+    strings: StringShape,
+    array: Option<Box<ArrayShape>>,
+    object: Option<Box<ObjectShape>>,
+}
+
+impl Shape {
+    fn observe<T: JsonType>(&mut self, value: &T, options: InferenceOptions) {
+        self.count += 1;
+        match value.primitive_type() {
+            PrimitiveType::Null => self.nulls += 1,
+            PrimitiveType::Boolean => self.booleans += 1,
+            PrimitiveType::Integer => self.integers += 1,
+            PrimitiveType::Number => self.numbers += 1,
+            PrimitiveType::String => {
+                self.strings.count += 1;
+                if !self.strings.has_too_many_values {
+                    let _ = self.strings.values.insert(value.as_string().unwrap_or_default().to_string());
+                    if self.strings.values.len() > options.max_enum_values {
+                        self.strings.has_too_many_values = true;
+                        self.strings.values.clear();
+                    }
+                }
+            }
+            PrimitiveType::Array => {
+                let array_shape = self.array.get_or_insert_with(Box::default);
+                array_shape.count += 1;
+                for item in value.as_array().into_iter().flatten() {
+                    array_shape.items.observe(item, options);
+                }
+            }
+            PrimitiveType::Object => {
+                let object_shape = self.object.get_or_insert_with(Box::default);
+                object_shape.count += 1;
+                if let Some(object) = value.as_object() {
+                    for key in object.keys() {
+                        if let Some(attribute_value) = value.get_attribute(key) {
+                            object_shape.properties.entry(key.to_string()).or_default().observe(attribute_value, options);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn to_schema(&self, options: InferenceOptions) -> IndexMap<String, RustType> {
+        let mut schema = IndexMap::new();
+
+        // Integers and numbers are merged into `number` as JSON Schema integers are a subset of numbers
+        let mut types = Vec::new();
+        if self.booleans > 0 {
+            types.push("boolean");
+        }
+        if self.numbers > 0 {
+            types.push("number");
+        } else if self.integers > 0 {
+            types.push("integer");
+        }
+        if self.strings.count > 0 {
+            types.push("string");
+        }
+        if self.array.is_some() {
+            types.push("array");
+        }
+        if self.object.is_some() {
+            types.push("object");
+        }
+        let has_only_strings = types == ["string"];
+        if self.nulls > 0 {
+            types.push("null");
+        }
+        match types.as_slice() {
+            [] => {}
+            [primitive_type] => {
+                let _ = schema.insert("type".to_string(), rust_type!(*primitive_type));
+            }
+            _ => {
+                let _ = schema.insert("type".to_string(), RustType::from(types.into_iter().map(RustType::from).collect::<Vec<_>>()));
+            }
+        }
+
+        // `enum` would reject the values of the other types, so it is emitted only for (nullable) strings
+        let strings = &self.strings;
+        if has_only_strings && !strings.has_too_many_values && !strings.values.is_empty() && strings.count >= 2 * strings.values.len() {
+            let mut values = strings.values.iter().map(|value| rust_type!(value.as_str())).collect::<Vec<_>>();
+            if self.nulls > 0 {
+                values.push(rust_type!(null));
+            }
+            let _ = schema.insert("enum".to_string(), RustType::from(values));
+        }
+
+        if let Some(array_shape) = &self.array {
+            if array_shape.items.count > 0 {
+                let _ = schema.insert("items".to_string(), RustType::Object(array_shape.items.to_schema(options)));
+            }
+        }
+
+        if let Some(object_shape) = &self.object {
+            let mut properties = IndexMap::new();
+            let mut required = Vec::new();
+            for (key, property_shape) in &object_shape.properties {
+                let _ = properties.insert(key.clone(), RustType::Object(property_shape.to_schema(options)));
+                #[allow(clippy::cast_precision_loss)]
+                let frequency = property_shape.count as f64 / object_shape.count as f64;
+                if frequency >= options.required_threshold {
+                    required.push(rust_type!(key.as_str()));
+                }
+            }
+            if !properties.is_empty() {
+                let _ = schema.insert("properties".to_string(), RustType::Object(properties));
+            }
+            if !required.is_empty() {
+                let _ = schema.insert("required".to_string(), RustType::from(required));
+            }
+        }
+        schema
+    }
+}
+
+/// Infer a Draft 2020-12 schema from `samples`, with the default options all the samples are valid against it
+///
+/// # Panics
+/// The function panics if a sample contains values not recognized as JSON primitive types
+#[must_use]
+pub fn infer_schema<'json, T: JsonType + 'json>(samples: impl Iterator<Item = &'json T>) -> RustType {
+    infer_schema_with_options(samples, InferenceOptions::default())
+}
+
+/// # Panics
+/// See `infer_schema`
+#[must_use]
+pub fn infer_schema_with_options<'json, T: JsonType + 'json>(samples: impl Iterator<Item = &'json T>, options: InferenceOptions) -> RustType {
+    let mut shape = Shape::default();
+    for sample in samples {
+        shape.observe(sample, options);
+    }
+    let mut schema = IndexMap::new();
+    let _ = schema.insert("$schema".to_string(), rust_type!(DRAFT_2020_12_META_SCHEMA));
+    schema.extend(shape.to_schema(options));
+    RustType::Object(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{infer_schema, infer_schema_with_options, InferenceOptions};
+    use crate::rust_type_impl::RustType;
+
+    #[test]
+    fn test_empty_samples() {
+        assert_eq!(
+            infer_schema(Vec::<RustType>::new().iter()),
+            rust_type!({"$schema": "https://json-schema.org/draft/2020-12/schema"})
+        );
+    }
+
+    #[test]
+    fn test_infer_schema() {
+        let samples = [
+            rust_type!({"id": 1, "status": "active", "score": 1, "tags": ["a"], "parent": null}),
+            rust_type!({"id": 2, "status": "inactive", "score": 2.5, "tags": [], "parent": {"id": 1}}),
+            rust_type!({"id": 3, "status": "active", "score": 3, "tags": ["b", "c"], "nickname": "x"}),
+            rust_type!({"id": 4, "status": "active", "score": 4, "tags": ["d"], "parent": null}),
+        ];
+        assert_eq!(
+            infer_schema(samples.iter()),
+            rust_type!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "status": {"type": "string", "enum": ["active", "inactive"]},
+                    "score": {"type": "number"},
+                    "tags": {"type": "array", "items": {"type": "string"}},
+                    "parent": {"type": ["object", "null"], "properties": {"id": {"type": "integer"}}, "required": ["id"]},
+                    "nickname": {"type": "string"},
+                },
+                "required": ["id", "status", "score", "tags"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_nullable_enum_and_mixed_types() {
+        let samples = [rust_type!(["a", null, "a", "b", "b"]), rust_type!([true, "b"])];
+        assert_eq!(
+            infer_schema_with_options(samples[..1].iter(), InferenceOptions::default()),
+            rust_type!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "array",
+                "items": {"type": ["string", "null"], "enum": ["a", "b", null]},
+            })
+        );
+        // `enum` would reject booleans
+        assert_eq!(
+            infer_schema(samples.iter()),
+            rust_type!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "array",
+                "items": {"type": ["boolean", "string", "null"]},
+            })
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let samples = [
+            rust_type!({"a": "x", "b": 1}),
+            rust_type!({"a": "x"}),
+            rust_type!({"a": "y"}),
+            rust_type!({"a": "y", "b": 2}),
+        ];
+        let options = InferenceOptions::default().with_max_enum_values(0).with_required_threshold(0.5);
+        assert_eq!(
+            infer_schema_with_options(samples.iter(), options),
+            rust_type!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {"a": {"type": "string"}, "b": {"type": "integer"}},
+                "required": ["a", "b"],
+            })
+        );
+    }
+}
+
+#[cfg(all(test, feature = "schema"))]
+mod tests_schema {
+    use super::infer_schema;
+    use crate::schema::JsonSchema;
+
+    #[test]
+    fn test_samples_are_valid_against_the_inferred_schema() {
+        let samples = [
+            rust_type!({"id": 1, "status": "active", "tags": ["a"], "parent": null}),
+            rust_type!({"id": 2, "status": "inactive", "tags": [], "parent": {"id": 1}}),
+            rust_type!({"id": 3.5, "status": "active", "tags": ["b", 1], "nickname": "x"}),
+        ];
+        let schema = JsonSchema::compile(&infer_schema(samples.iter())).unwrap();
+        assert!(samples.iter().all(|sample| schema.is_valid(sample)));
+    }
+}
+
+#[cfg(all(test, feature = "trait_serde_json"))]
+mod tests_serde_json {
+    use super::infer_schema;
+
+    #[test]
+    fn test_infer_schema_from_serde_json_values() {
+        let samples = [json!({"code": 200}), json!({"code": 404})];
+        assert_eq!(
+            infer_schema(samples.iter()),
+            rust_type!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {"code": {"type": "integer"}},
+                "required": ["code"],
+            })
+        );
+    }
+}
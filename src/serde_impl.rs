@@ -0,0 +1,211 @@
+// serde integration: RustType is (de)serializable and any JsonType can be serialized via SerializeJsonType
+use crate::{
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+    rust_type_impl::RustType,
+};
+use indexmap::IndexMap;
+use serde::{
+    de::{Deserialize, Deserializer, Error as DeError, MapAccess, SeqAccess, Visitor},
+    ser::{Error as SerError, Serialize, SerializeMap, SerializeSeq, Serializer},
+};
+use std::{convert::TryFrom, fmt};
+
+// Adapter allowing any JsonType (ie. `&PyAny`) to be consumed by serde based libraries (ie. `serde_json::to_writer`).
+// Values not recognized as any JSON primitive type are reported as serialization errors.
+#[derive(Debug)]
+pub struct SerializeJsonType<'json, T: JsonType>(pub &'json T);
+
+impl<T: JsonType> Serialize for SerializeJsonType<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.0;
+        match value.try_primitive_type().map_err(S::Error::custom)? {
+            PrimitiveType::Array => {
+                let items = value.as_array();
+                let mut seq = serializer.serialize_seq(Some(items.as_ref().map_or(0, ExactSizeIterator::len)))?;
+                for item in items.into_iter().flatten() {
+                    seq.serialize_element(&SerializeJsonType(item))?;
+                }
+                seq.end()
+            }
+            PrimitiveType::Boolean => serializer.serialize_bool(value.as_boolean().unwrap_or_default()),
+            PrimitiveType::Integer => {
+                let integer = value.as_integer().unwrap_or_default();
+                // Prefer the 64 bits representations as not all the serializers support 128 bits integers
+                if let Ok(integer) = i64::try_from(integer) {
+                    serializer.serialize_i64(integer)
+                } else if let Ok(integer) = u64::try_from(integer) {
+                    serializer.serialize_u64(integer)
+                } else {
+                    serializer.serialize_i128(integer)
+                }
+            }
+            PrimitiveType::Null => serializer.serialize_unit(),
+            PrimitiveType::Number => serializer.serialize_f64(value.as_number().unwrap_or_default()),
+            PrimitiveType::Object => {
+                let keys = value
+                    .as_object()
+                    .map(|object| object.keys().map(ToString::to_string).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let mut map = serializer.serialize_map(Some(keys.len()))?;
+                for key in keys {
+                    if let Some(attribute_value) = value.get_attribute(&key) {
+                        map.serialize_entry(&key, &SerializeJsonType(attribute_value))?;
+                    }
+                }
+                map.end()
+            }
+            PrimitiveType::String => serializer.serialize_str(value.as_string().unwrap_or_default()),
+        }
+    }
+}
+
+impl Serialize for RustType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeJsonType(self).serialize(serializer)
+    }
+}
+
+struct RustTypeVisitor;
+
+impl<'de> Visitor<'de> for RustTypeVisitor {
+    type Value = RustType;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a JSON-like value")
+    }
+
+    fn visit_bool<E: DeError>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(RustType::Boolean(value))
+    }
+
+    fn visit_i64<E: DeError>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(RustType::Integer(i128::from(value)))
+    }
+
+    fn visit_i128<E: DeError>(self, value: i128) -> Result<Self::Value, E> {
+        Ok(RustType::Integer(value))
+    }
+
+    fn visit_u64<E: DeError>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(RustType::Integer(i128::from(value)))
+    }
+
+    fn visit_u128<E: DeError>(self, value: u128) -> Result<Self::Value, E> {
+        i128::try_from(value)
+            .map(RustType::Integer)
+            .map_err(|_| E::custom(format!("integer `{value}` is out of the supported range")))
+    }
+
+    fn visit_f64<E: DeError>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(RustType::Number(value))
+    }
+
+    fn visit_str<E: DeError>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(RustType::String(value.to_string()))
+    }
+
+    fn visit_string<E: DeError>(self, value: String) -> Result<Self::Value, E> {
+        Ok(RustType::String(value))
+    }
+
+    fn visit_unit<E: DeError>(self) -> Result<Self::Value, E> {
+        Ok(RustType::Null)
+    }
+
+    fn visit_none<E: DeError>(self) -> Result<Self::Value, E> {
+        Ok(RustType::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        RustType::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(RustType::List(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut object = IndexMap::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some((key, value)) = map.next_entry::<String, RustType>()? {
+            let _ = object.insert(key, value);
+        }
+        Ok(RustType::Object(object))
+    }
+}
+
+impl<'de> Deserialize<'de> for RustType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RustTypeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SerializeJsonType;
+    use crate::{rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use test_case::test_case;
+
+    #[test_case(&rust_type!(null), "null" ; "null")]
+    #[test_case(&rust_type!(true), "true" ; "boolean")]
+    #[test_case(&rust_type!(-1), "-1" ; "integer")]
+    #[test_case(&RustType::from(i128::from(u64::MAX)), "18446744073709551615" ; "u64 integer")]
+    #[test_case(&rust_type!(1.5), "1.5" ; "number")]
+    #[test_case(&rust_type!("a\"b"), r#""a\"b""# ; "string")]
+    #[test_case(&rust_type!([1, [], {}]), "[1,[],{}]" ; "array")]
+    #[test_case(&rust_type!({"z": 1, "a": [null]}), r#"{"z":1,"a":[null]}"# ; "object")]
+    fn test_serialize_rust_type(value: &RustType, expected_json: &str) {
+        assert_eq!(serde_json::to_string(value).unwrap(), expected_json);
+    }
+
+    #[test_case("null", &rust_type!(null) ; "null")]
+    #[test_case("false", &rust_type!(false) ; "boolean")]
+    #[test_case("-1", &rust_type!(-1) ; "integer")]
+    #[test_case("18446744073709551615", &RustType::from(i128::from(u64::MAX)) ; "u64 integer")]
+    #[test_case("1.5", &rust_type!(1.5) ; "number")]
+    #[test_case(r#"[1, "a", [], {}]"#, &rust_type!([1, "a", [], {}]) ; "array")]
+    #[test_case(r#"{"z": 1, "a": {"b": null}}"#, &rust_type!({"z": 1, "a": {"b": null}}) ; "object")]
+    fn test_deserialize_rust_type(json_str: &str, expected_value: &RustType) {
+        assert_eq!(&serde_json::from_str::<RustType>(json_str).unwrap(), expected_value);
+    }
+
+    #[test]
+    fn test_deserialize_preserves_key_order() {
+        let value = serde_json::from_str::<RustType>(r#"{"z": 1, "a": 2, "m": 3}"#).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"z":1,"a":2,"m":3}"#);
+    }
+
+    #[test]
+    fn test_serialize_json_type_is_equivalent_to_rust_type() {
+        let value = rust_type!({"a": [1, 2.5, "c"], "d": {"e": null}});
+        assert_eq!(serde_json::to_value(SerializeJsonType(&value)).unwrap(), serde_json::to_value(&value).unwrap());
+    }
+
+    #[test]
+    fn test_serialize_unrecognized_value() {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        assert_eq!(
+            serde_json::to_string(&SerializeJsonType(&value)).unwrap_err().to_string(),
+            "The value does not match any JSON primitive type"
+        );
+    }
+
+    #[cfg(feature = "trait_serde_yaml")]
+    #[test]
+    fn test_serialize_serde_yaml_value() {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str("z: [1, 2.5, ~]\na: {b: true}").unwrap();
+        assert_eq!(serde_json::to_string(&SerializeJsonType(&yaml_value)).unwrap(), r#"{"z":[1,2.5,null],"a":{"b":true}}"#);
+    }
+
+    #[cfg(feature = "trait_json")]
+    #[test]
+    fn test_serialize_json_value() {
+        let json_value = json::parse(r#"{"a": [1, true, "b"]}"#).unwrap();
+        let mut buffer = Vec::new();
+        serde_json::to_writer(&mut buffer, &SerializeJsonType(&json_value)).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), r#"{"a":[1,true,"b"]}"#);
+    }
+}
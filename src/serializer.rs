@@ -0,0 +1,597 @@
+// Serialization of JsonType instances according to RFC 8259 (https://tools.ietf.org/html/rfc8259)
+use crate::{
+    error::Error,
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+};
+use std::{
+    convert::TryFrom,
+    fmt::{self, Write},
+    io,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsonFormat {
+    // No whitespaces between the tokens
+    Compact,
+    // Each array item and object attribute is on its own line, indented according to its nesting level
+    Pretty,
+    // JSON Canonicalization Scheme (RFC 8785: https://tools.ietf.org/html/rfc8785): no whitespaces, object attributes sorted by
+    // the UTF-16 code units of their keys and numbers formatted as ECMAScript does.
+    // NOTE: `SerializerOptions::escape_non_ascii` and `SerializerOptions::non_finite_numbers` are ignored as the scheme
+    // requires minimal escaping and does not allow non-finite numbers
+    Canonical,
+}
+
+// JSON does not allow the representation of NaN and infinite numbers
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NonFiniteNumberPolicy {
+    Error,
+    Null,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerializerOptions {
+    pub format: JsonFormat,
+    // Indentation of each nesting level, relevant only for JsonFormat::Pretty
+    pub indent: String,
+    // Escape all the non-ASCII characters as \uXXXX sequences (UTF-16 surrogate pairs are used outside of the Basic Multilingual Plane)
+    pub escape_non_ascii: bool,
+    pub non_finite_numbers: NonFiniteNumberPolicy,
+}
+
+impl Default for SerializerOptions {
+    fn default() -> Self {
+        Self::compact()
+    }
+}
+
+impl SerializerOptions {
+    #[must_use]
+    pub fn compact() -> Self {
+        Self {
+            format: JsonFormat::Compact,
+            indent: "  ".to_string(),
+            escape_non_ascii: false,
+            non_finite_numbers: NonFiniteNumberPolicy::Error,
+        }
+    }
+
+    #[must_use]
+    pub fn pretty() -> Self {
+        Self {
+            format: JsonFormat::Pretty,
+            ..Self::compact()
+        }
+    }
+
+    #[must_use]
+    pub fn canonical() -> Self {
+        Self {
+            format: JsonFormat::Canonical,
+            ..Self::compact()
+        }
+    }
+
+    #[must_use]
+    pub fn with_indent(mut self, indent: &str) -> Self {
+        self.indent = indent.to_string();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_escape_non_ascii(mut self, escape_non_ascii: bool) -> Self {
+        self.escape_non_ascii = escape_non_ascii;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_non_finite_numbers(mut self, non_finite_numbers: NonFiniteNumberPolicy) -> Self {
+        self.non_finite_numbers = non_finite_numbers;
+        self
+    }
+}
+
+// Integers outside of the I-JSON range (RFC 7493) cannot be exactly represented as IEEE 754 double precision values
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+// IEEE 754 double precision value equal to `integer`, None if `integer` would be rounded.
+// Integers outside of the I-JSON range are accepted only if exactly representable, so integral values are formatted
+// in the same way regardless of the backend reporting them as integers (ie. `1e30` for the json backend) or as numbers
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn exact_f64(integer: i128) -> Option<f64> {
+    let number = integer as f64;
+    // The float to integer cast saturates, so 2^127 (the rounding of i128::MAX) is excluded explicitly
+    if integer.unsigned_abs() <= MAX_SAFE_INTEGER.unsigned_abs() || (number.abs() < 2_f64.powi(127) && number as i128 == integer) {
+        Some(number)
+    } else {
+        None
+    }
+}
+
+impl From<fmt::Error> for Error {
+    fn from(_: fmt::Error) -> Self {
+        Self::WriteFailed {
+            reason: "formatter error".to_string(),
+        }
+    }
+}
+
+// Representation of the values not recognized as any JSON primitive type by the lossy serialization.
+// It is not valid JSON on purpose, so it cannot be mistaken for a recognized value.
+const UNRECOGNIZED_VALUE_PLACEHOLDER: &str = "<unrecognized>";
+
+struct Serializer<'options, W: Write> {
+    options: &'options SerializerOptions,
+    writer: W,
+    depth: usize,
+    // Write UNRECOGNIZED_VALUE_PLACEHOLDER instead of failing with Error::UnrecognizedValue
+    is_lossy: bool,
+}
+
+impl<W: Write> Serializer<'_, W> {
+    fn write_new_line(&mut self) -> Result<(), Error> {
+        if self.options.format == JsonFormat::Pretty {
+            self.writer.write_char('\n')?;
+            for _ in 0..self.depth {
+                self.writer.write_str(&self.options.indent)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_canonical(&self) -> bool {
+        self.options.format == JsonFormat::Canonical
+    }
+
+    fn write_number(&mut self, value: f64) -> Result<(), Error> {
+        if self.is_canonical() {
+            if value.is_finite() {
+                self.writer.write_str(&ecmascript_number_to_string(value))?;
+                Ok(())
+            } else {
+                Err(Error::NonFiniteNumber { value: format!("{value}") })
+            }
+        } else if value.is_finite() {
+            // Debug formatting preserves the decimal point of integral values (ie. 1.0) and uses the exponent notation for very large or small values
+            write!(self.writer, "{value:?}")?;
+            Ok(())
+        } else {
+            match self.options.non_finite_numbers {
+                NonFiniteNumberPolicy::Error => Err(Error::NonFiniteNumber { value: format!("{value}") }),
+                NonFiniteNumberPolicy::Null => {
+                    self.writer.write_str("null")?;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn write_string(&mut self, value: &str) -> Result<(), Error> {
+        self.writer.write_char('"')?;
+        // Characters that do not need escaping are written in chunks
+        let mut chunk_start = 0;
+        for (index, character) in value.char_indices() {
+            let escape_non_ascii = self.options.escape_non_ascii && !self.is_canonical() && !character.is_ascii();
+            let short_escape = match character {
+                '"' => Some("\\\""),
+                '\\' => Some("\\\\"),
+                '\u{8}' => Some("\\b"),
+                '\u{c}' => Some("\\f"),
+                '\n' => Some("\\n"),
+                '\r' => Some("\\r"),
+                '\t' => Some("\\t"),
+                _ => None,
+            };
+            if short_escape.is_none() && !character.is_control() && !escape_non_ascii {
+                continue;
+            }
+
+            self.writer.write_str(&value[chunk_start..index])?;
+            chunk_start = index + character.len_utf8();
+            if let Some(escape) = short_escape {
+                self.writer.write_str(escape)?;
+            } else if character < ' ' || escape_non_ascii {
+                let mut buffer = [0_u16; 2];
+                for code_unit in character.encode_utf16(&mut buffer) {
+                    write!(self.writer, "\\u{code_unit:04x}")?;
+                }
+            } else {
+                // Control characters outside of the U+0000 - U+001F range do not need to be escaped
+                self.writer.write_char(character)?;
+            }
+        }
+        self.writer.write_str(&value[chunk_start..])?;
+        self.writer.write_char('"')?;
+        Ok(())
+    }
+
+    fn write_value<T: JsonType>(&mut self, value: &T) -> Result<(), Error> {
+        let primitive_type = match value.try_primitive_type() {
+            Ok(primitive_type) => primitive_type,
+            Err(Error::UnrecognizedValue) if self.is_lossy => {
+                self.writer.write_str(UNRECOGNIZED_VALUE_PLACEHOLDER)?;
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        match primitive_type {
+            PrimitiveType::Null => self.writer.write_str("null")?,
+            PrimitiveType::Boolean => {
+                if let Some(boolean) = value.as_boolean() {
+                    write!(self.writer, "{boolean}")?;
+                }
+            }
+            PrimitiveType::Integer => {
+                if let Some(integer) = value.as_integer() {
+                    if self.is_canonical() {
+                        // RFC 8785 represents all the numbers as IEEE 754 double precision values, so integers that would be rounded are rejected
+                        self.write_number(exact_f64(integer).ok_or(Error::IntegerOutOfRange { value: integer })?)?;
+                    } else {
+                        write!(self.writer, "{integer}")?;
+                    }
+                }
+            }
+            PrimitiveType::Number => {
+                if let Some(number) = value.as_number() {
+                    self.write_number(number)?;
+                }
+            }
+            PrimitiveType::String => {
+                if let Some(string) = value.as_string() {
+                    self.write_string(string)?;
+                }
+            }
+            PrimitiveType::Array => {
+                if let Some(items) = value.as_array() {
+                    self.writer.write_char('[')?;
+                    if items.len() > 0 {
+                        self.depth += 1;
+                        for (index, item) in items.enumerate() {
+                            if index > 0 {
+                                self.writer.write_char(',')?;
+                            }
+                            self.write_new_line()?;
+                            self.write_value(item)?;
+                        }
+                        self.depth -= 1;
+                        self.write_new_line()?;
+                    }
+                    self.writer.write_char(']')?;
+                }
+            }
+            PrimitiveType::Object => {
+                if let Some(object) = value.as_object() {
+                    self.writer.write_char('{')?;
+                    let mut is_empty = true;
+                    self.depth += 1;
+                    let items: Box<dyn Iterator<Item = (&str, &T)>> = if self.is_canonical() {
+                        let mut items = object.items().collect::<Vec<_>>();
+                        items.sort_by(|(key1, _), (key2, _)| key1.encode_utf16().cmp(key2.encode_utf16()));
+                        Box::new(items.into_iter())
+                    } else {
+                        object.items()
+                    };
+                    for (key, attribute_value) in items {
+                        if !is_empty {
+                            self.writer.write_char(',')?;
+                        }
+                        is_empty = false;
+                        self.write_new_line()?;
+                        self.write_string(key)?;
+                        self.writer.write_str(if self.options.format == JsonFormat::Pretty { ": " } else { ":" })?;
+                        self.write_value(attribute_value)?;
+                    }
+                    self.depth -= 1;
+                    if !is_empty {
+                        self.write_new_line()?;
+                    }
+                    self.writer.write_char('}')?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Format a finite number according to the ECMAScript Number::toString algorithm (https://tc39.es/ecma262/#sec-numeric-types-number-tostring)
+fn ecmascript_number_to_string(value: f64) -> String {
+    if value == 0.0 {
+        // Negative zero is represented as 0 as well
+        return "0".to_string();
+    }
+    // LowerExp formatting provides the shortest sequence of digits that identifies the number (ie. "-1.2345e-7")
+    let exponential_representation = format!("{:e}", value.abs());
+    let (mantissa, exponent) = exponential_representation.split_at(exponential_representation.find('e').unwrap_or(exponential_representation.len()));
+    let digits = mantissa.replace('.', "");
+    let digits_count = i32::try_from(digits.len()).unwrap_or(i32::MAX);
+    // position of the decimal point with respect to the digits
+    let point_position = exponent.trim_start_matches('e').parse::<i32>().unwrap_or(0) + 1;
+
+    let mut result = if value < 0.0 { "-".to_string() } else { String::new() };
+    if digits_count <= point_position && point_position <= 21 {
+        result.push_str(&digits);
+        result.extend(std::iter::repeat_n('0', usize::try_from(point_position - digits_count).unwrap_or(0)));
+    } else if 0 < point_position && point_position <= 21 {
+        let (integer_part, fractional_part) = digits.split_at(usize::try_from(point_position).unwrap_or(0));
+        result.push_str(integer_part);
+        result.push('.');
+        result.push_str(fractional_part);
+    } else if -6 < point_position && point_position <= 0 {
+        result.push_str("0.");
+        result.extend(std::iter::repeat_n('0', usize::try_from(-point_position).unwrap_or(0)));
+        result.push_str(&digits);
+    } else {
+        let (first_digit, other_digits) = digits.split_at(1);
+        result.push_str(first_digit);
+        if !other_digits.is_empty() {
+            result.push('.');
+            result.push_str(other_digits);
+        }
+        let _ = write!(result, "e{}{}", if point_position > 0 { "+" } else { "-" }, (point_position - 1).abs());
+    }
+    result
+}
+
+pub(crate) fn write_json_fmt<T: JsonType, W: Write>(value: &T, writer: W, options: &SerializerOptions) -> Result<(), Error> {
+    Serializer {
+        options,
+        writer,
+        depth: 0,
+        is_lossy: false,
+    }
+    .write_value(value)
+}
+
+// Adapter that allows the serializer to write into io::Write instances, the io::Error is preserved
+// as fmt::Error cannot carry any information
+struct IoWriter<W: io::Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        self.writer.write_all(value.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Serialize `value` into `writer` according to `options`.
+/// The JSON representation is written incrementally, so no intermediate copy of `value` (or of its representation) is allocated.
+///
+/// NOTE: Writes are performed through an internal buffer, so there is no need to provide a buffered `writer`
+///
+/// # Errors
+/// `Error::NonFiniteNumber` is returned if `value` contains NaN or infinite numbers and `options.non_finite_numbers` is `NonFiniteNumberPolicy::Error`.
+/// `Error::UnrecognizedValue` is returned if `value` contains values not recognized as any JSON primitive type.
+/// `Error::WriteFailed` is returned if `writer` fails.
+pub fn write_json<T: JsonType, W: io::Write>(value: &T, writer: W, options: &SerializerOptions) -> Result<(), Error> {
+    let mut io_writer = IoWriter {
+        writer: io::BufWriter::new(writer),
+        error: None,
+    };
+    let result =
+        write_json_fmt(value, &mut io_writer, options).and_then(|()| io::Write::flush(&mut io_writer.writer).map_err(|error| Error::WriteFailed { reason: error.to_string() }));
+    io_writer.error.map_or(result, |error| Err(Error::WriteFailed { reason: error.to_string() }))
+}
+
+/// Serialize `value` as a JSON string according to `options`
+///
+/// # Errors
+/// `Error::NonFiniteNumber` is returned if `value` contains NaN or infinite numbers and `options.non_finite_numbers` is `NonFiniteNumberPolicy::Error`.
+/// `Error::UnrecognizedValue` is returned if `value` contains values not recognized as any JSON primitive type.
+pub fn to_json_string_with_options<T: JsonType>(value: &T, options: &SerializerOptions) -> Result<String, Error> {
+    let mut result = String::new();
+    write_json_fmt(value, &mut result, options)?;
+    Ok(result)
+}
+
+/// Serialize `value` according to the JSON Canonicalization Scheme (RFC 8785).
+/// The output is byte-identical for equal values, regardless of the `JsonType` implementation holding them.
+///
+/// NOTE: As mandated by the RFC, integers are represented as IEEE 754 double precision values, so integers outside of
+/// the I-JSON range (absolute value greater than 2^53 - 1) are accepted only if they are exactly representable (ie. `10^21`)
+///
+/// # Errors
+/// `Error::NonFiniteNumber` is returned if `value` contains NaN or infinite numbers and `Error::IntegerOutOfRange` if
+/// `value` contains integers that would be rounded.
+/// `Error::UnrecognizedValue` is returned if `value` contains values not recognized as any JSON primitive type.
+pub fn to_canonical_json_string<T: JsonType>(value: &T) -> Result<String, Error> {
+    to_json_string_with_options(value, &SerializerOptions::canonical())
+}
+
+// Compact serialization used by JsonTypeToString, non-finite numbers are represented as null (as serde_json does)
+// and values not recognized as any JSON primitive type as `<unrecognized>`
+pub(crate) fn to_json_string_lossy<T: JsonType>(value: &T) -> String {
+    let options = SerializerOptions::compact().with_non_finite_numbers(NonFiniteNumberPolicy::Null);
+    let mut result = String::new();
+    // Writing into a String cannot fail as non-finite numbers and unrecognized values have a representation
+    let _ = Serializer {
+        options: &options,
+        writer: &mut result,
+        depth: 0,
+        is_lossy: true,
+    }
+    .write_value(value);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ecmascript_number_to_string, to_canonical_json_string, to_json_string_lossy, to_json_string_with_options, write_json, NonFiniteNumberPolicy, SerializerOptions};
+    use crate::{error::Error, rust_type_impl::RustType, test_helpers::PartiallyRecognizedValue};
+    use std::io;
+    use test_case::test_case;
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test_case(&rust_type!(null) => "null")]
+    #[test_case(&rust_type!(true) => "true")]
+    #[test_case(&rust_type!(-12) => "-12")]
+    #[test_case(&rust_type!(1.0) => "1.0" ; "integral number")]
+    #[test_case(&rust_type!(1e300) => "1e300" ; "large number")]
+    #[test_case(&rust_type!("a\"b\\c/d") => r#""a\"b\\c/d""# ; "quote and backslash")]
+    #[test_case(&rust_type!("\u{8}\u{c}\n\r\t\u{0}\u{1f}") => r#""\b\f\n\r\t\u0000\u001f""# ; "control characters")]
+    #[test_case(&rust_type!("\u{7f}àé😀") => "\"\u{7f}àé😀\"" ; "non ascii characters")]
+    #[test_case(&rust_type!([]) => "[]" ; "empty array")]
+    #[test_case(&rust_type!({}) => "{}" ; "empty object")]
+    #[test_case(&rust_type!({"k\ney": [1, {"a": null}]}) => r#"{"k\ney":[1,{"a":null}]}"# ; "nested values")]
+    fn test_compact(value: &RustType) -> String {
+        to_json_string_with_options(value, &SerializerOptions::compact()).unwrap()
+    }
+
+    #[test_case(&rust_type!([]), "  " => "[]" ; "empty array")]
+    #[test_case(&rust_type!([1, [2, {}]]), "  " => "[\n  1,\n  [\n    2,\n    {}\n  ]\n]" ; "nested arrays")]
+    #[test_case(&rust_type!({"a": {"b": [true]}}), "\t" => "{\n\t\"a\": {\n\t\t\"b\": [\n\t\t\ttrue\n\t\t]\n\t}\n}" ; "tab indent")]
+    fn test_pretty(value: &RustType, indent: &str) -> String {
+        to_json_string_with_options(value, &SerializerOptions::pretty().with_indent(indent)).unwrap()
+    }
+
+    #[test]
+    fn test_escape_non_ascii() {
+        assert_eq!(
+            to_json_string_with_options(&rust_type!("aé😀"), &SerializerOptions::compact().with_escape_non_ascii(true)),
+            Ok(r#""a\u00e9\ud83d\ude00""#.to_string())
+        );
+    }
+
+    #[test_case(f64::NAN, NonFiniteNumberPolicy::Error => Err(Error::NonFiniteNumber { value: "NaN".to_string() }) ; "nan error")]
+    #[test_case(f64::NEG_INFINITY, NonFiniteNumberPolicy::Error => Err(Error::NonFiniteNumber { value: "-inf".to_string() }) ; "infinite error")]
+    #[test_case(f64::INFINITY, NonFiniteNumberPolicy::Null => Ok("[null]".to_string()) ; "infinite null")]
+    fn test_non_finite_numbers(number: f64, policy: NonFiniteNumberPolicy) -> Result<String, Error> {
+        to_json_string_with_options(&rust_type!([number]), &SerializerOptions::compact().with_non_finite_numbers(policy))
+    }
+
+    #[test_case(&rust_type!({"a\u{0}\"": ["\u{2028}\\", 1.5, -3, null, {"b": false}]}))]
+    #[test_case(&rust_type!("\u{1}\u{10ffff}"))]
+    fn test_output_is_valid_json(value: &RustType) {
+        for options in &[
+            SerializerOptions::compact(),
+            SerializerOptions::pretty(),
+            SerializerOptions::compact().with_escape_non_ascii(true),
+        ] {
+            let serialized = to_json_string_with_options(value, options).unwrap();
+            let parsed = serde_json::from_str::<serde_json::Value>(&serialized).unwrap();
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                to_json_string_with_options(value, &SerializerOptions::compact()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_json() {
+        let value = rust_type!({"a": ["b\n", 1.5, {"c": null}]});
+        for options in &[SerializerOptions::compact(), SerializerOptions::pretty()] {
+            let mut output = Vec::new();
+            write_json(&value, &mut output, options).unwrap();
+            assert_eq!(String::from_utf8(output).unwrap(), to_json_string_with_options(&value, options).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_write_json_reports_writer_errors() {
+        assert_eq!(
+            write_json(&rust_type!([1, 2]), FailingWriter, &SerializerOptions::compact()),
+            Err(Error::WriteFailed { reason: "disk full".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_write_json_reports_non_finite_numbers() {
+        let mut output = Vec::new();
+        assert_eq!(
+            write_json(&rust_type!([1, f64::NAN]), &mut output, &SerializerOptions::compact()),
+            Err(Error::NonFiniteNumber { value: "NaN".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_values_are_reported() {
+        let value = PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1), PartiallyRecognizedValue::Unrecognized]);
+        assert_eq!(to_json_string_with_options(&value, &SerializerOptions::compact()), Err(Error::UnrecognizedValue));
+        assert_eq!(to_canonical_json_string(&value), Err(Error::UnrecognizedValue));
+        assert_eq!(write_json(&value, Vec::new(), &SerializerOptions::compact()), Err(Error::UnrecognizedValue));
+        assert_eq!(
+            to_json_string_with_options(&PartiallyRecognizedValue::Array(vec![PartiallyRecognizedValue::Integer(1)]), &SerializerOptions::compact()),
+            Ok("[1]".to_string())
+        );
+        assert_eq!(to_json_string_lossy(&value), "[1,<unrecognized>]");
+    }
+
+    // Examples from RFC 8785 Appendix B
+    #[test_case(-0.0 => "0" ; "negative zero")]
+    #[test_case(5e-324 => "5e-324" ; "min positive number")]
+    #[test_case(-1.797_693_134_862_315_7e308 => "-1.7976931348623157e+308" ; "min number")]
+    #[test_case(9_007_199_254_740_992.0 => "9007199254740992" ; "max safe integer plus one")]
+    #[test_case(295_147_905_179_352_830_000.0 => "295147905179352830000" ; "largest integer without exponent")]
+    #[test_case(1e21 => "1e+21" ; "smallest number with positive exponent")]
+    #[test_case(9.999_999_999_999_997e22 => "9.999999999999997e+22" ; "large number with positive exponent")]
+    #[test_case(0.000_001 => "0.000001" ; "smallest number without exponent")]
+    #[test_case(1e-7 => "1e-7" ; "largest number with negative exponent")]
+    #[test_case(333_333_333.333_333_3 => "333333333.3333333" ; "number with fractional part")]
+    #[test_case(-4.5 => "-4.5" ; "negative number")]
+    fn test_ecmascript_number_to_string(value: f64) -> String {
+        ecmascript_number_to_string(value)
+    }
+
+    #[allow(clippy::excessive_precision)]
+    #[test]
+    fn test_canonical_rfc_example() {
+        // Example from RFC 8785 Section 3.2.2
+        let value = rust_type!({
+            "numbers": [333_333_333.333_333_29, 1E30, 4.50, 2e-3, 0.000_000_000_000_000_000_000_000_001],
+            "string": "\u{20ac}$\u{f}\nA'\u{42}\u{22}\u{5c}\\\"/",
+            "literals": [null, true, false]
+        });
+        assert_eq!(
+            to_canonical_json_string(&value),
+            Ok(r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_canonical_keys_are_sorted_by_utf16_code_units() {
+        // Example from RFC 8785 Section 3.2.3
+        let value = rust_type!({"\u{20ac}": 1, "\r": 2, "\u{fb33}": 3, "1": 4, "\u{1f600}": 5, "\u{80}": 6, "\u{f6}": 7});
+        assert_eq!(
+            to_canonical_json_string(&value),
+            Ok("{\"\\r\":2,\"1\":4,\"\u{80}\":6,\"\u{f6}\":7,\"\u{20ac}\":1,\"\u{1f600}\":5,\"\u{fb33}\":3}".to_string())
+        );
+    }
+
+    #[test_case(&rust_type!({"a": [1, 2.0, 1e21]}) => Ok(r#"{"a":[1,2,1e+21]}"#.to_string()) ; "integers and numbers")]
+    #[test_case(&rust_type!([f64::NAN]) => Err(Error::NonFiniteNumber { value: "NaN".to_string() }) ; "non finite number")]
+    #[test_case(&rust_type!([9_007_199_254_740_991_i64, -9_007_199_254_740_991_i64]) => Ok("[9007199254740991,-9007199254740991]".to_string()) ; "largest safe integers")]
+    #[test_case(&rust_type!([9_007_199_254_740_993_i64]) => Err(Error::IntegerOutOfRange { value: 9_007_199_254_740_993 }) ; "integer above the safe range")]
+    #[test_case(&rust_type!([-9_007_199_254_740_993_i64]) => Err(Error::IntegerOutOfRange { value: -9_007_199_254_740_993 }) ; "integer below the safe range")]
+    #[test_case(&rust_type!([-9_007_199_254_740_992_i64, 1_000_000_000_000_000_000_000_i128]) => Ok("[-9007199254740992,1e+21]".to_string()) ; "exactly representable integers outside of the safe range")]
+    #[test_case(&rust_type!([i128::MAX]) => Err(Error::IntegerOutOfRange { value: i128::MAX }) ; "largest integer")]
+    fn test_canonical(value: &RustType) -> Result<String, Error> {
+        to_canonical_json_string(value)
+    }
+
+    #[cfg(all(feature = "trait_serde_json", feature = "trait_serde_yaml", feature = "trait_json"))]
+    #[test]
+    fn test_canonical_is_backend_independent() {
+        use crate::json_type::JsonTypeToCanonicalString;
+
+        let serialized = r#"{"z": [1, 2.5, 2.5e-7, 1e30, -1e19, "\u00e9"], "a": {"c": null, "b": true}}"#;
+        let expected = Ok(r#"{"a":{"b":true,"c":null},"z":[1,2.5,2.5e-7,1e+30,-10000000000000000000,"é"]}"#.to_string());
+        assert_eq!(serde_json::from_str::<serde_json::Value>(serialized).unwrap().to_canonical_json_string(), expected);
+        assert_eq!(serde_yaml::from_str::<serde_yaml::Value>(serialized).unwrap().to_canonical_json_string(), expected);
+        assert_eq!(json::parse(serialized).unwrap().to_canonical_json_string(), expected);
+    }
+}
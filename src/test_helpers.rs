@@ -0,0 +1,64 @@
+// Test-only JsonType implementation holding values that are not recognized as any JSON primitive type,
+// as it could happen for backends wrapping dynamically typed objects (ie. a Python `set`)
+use crate::json_type::{JsonMap, JsonType, ToRustType};
+
+// `pub(crate)` is needed as `unreachable_pub` is denied
+#[allow(clippy::redundant_pub_crate)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PartiallyRecognizedValue {
+    Unrecognized,
+    Integer(i128),
+    Array(Vec<Self>),
+}
+
+impl ToRustType for PartiallyRecognizedValue {}
+
+impl JsonType for PartiallyRecognizedValue {
+    fn as_array<'json>(&'json self) -> Option<Box<dyn ExactSizeIterator<Item = &'json Self> + 'json>> {
+        if let Self::Array(items) = self {
+            Some(Box::new(items.iter()))
+        } else {
+            None
+        }
+    }
+
+    fn as_boolean(&self) -> Option<bool> {
+        None
+    }
+
+    fn as_integer(&self) -> Option<i128> {
+        if let Self::Integer(integer) = self {
+            Some(*integer)
+        } else {
+            None
+        }
+    }
+
+    fn as_null(&self) -> Option<()> {
+        None
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        None
+    }
+
+    fn as_object(&self) -> Option<JsonMap<'_, Self>> {
+        None
+    }
+
+    fn as_string(&self) -> Option<&str> {
+        None
+    }
+
+    fn get_attribute(&self, _: &str) -> Option<&Self> {
+        None
+    }
+
+    fn get_index(&self, index: usize) -> Option<&Self> {
+        if let Self::Array(items) = self {
+            items.get(index)
+        } else {
+            None
+        }
+    }
+}
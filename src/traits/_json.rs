@@ -1,9 +1,10 @@
 use crate::{
-    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeToString, ThreadSafeJsonType, ToRustType},
+    convert::{from_i128_with_float_fallback, try_from_i128_without_fallback},
+    error::Error,
+    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeBuilder, JsonTypeMut, JsonTypeToString, PrimitiveType, ThreadSafeJsonType, ToRustType},
     rust_type_impl::RustType,
 };
-use json::JsonValue;
-use std::ops::Index;
+use json::{object::Object, JsonValue};
 
 impl Into<RustType> for JsonValue {
     fn into(self) -> RustType {
@@ -13,6 +14,52 @@ impl Into<RustType> for JsonValue {
 
 impl ToRustType for JsonValue {}
 
+impl JsonTypeBuilder for JsonValue {
+    fn null() -> Self {
+        Self::Null
+    }
+
+    fn from_bool(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+
+    fn from_i128(value: i128) -> Self {
+        from_i128_with_float_fallback(value)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        Self::from(value)
+    }
+
+    fn try_from_i128(value: i128) -> Option<Self> {
+        try_from_i128_without_fallback(value)
+    }
+
+    fn try_from_f64(value: f64) -> Option<Self> {
+        if value.is_finite() {
+            Some(Self::from(value))
+        } else {
+            None
+        }
+    }
+
+    fn from_string(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+
+    fn from_array<I: IntoIterator<Item = Self>>(items: I) -> Self {
+        Self::Array(items.into_iter().collect())
+    }
+
+    fn from_object<I: IntoIterator<Item = (String, Self)>>(items: I) -> Self {
+        let mut object = Object::new();
+        for (key, value) in items {
+            object.insert(&key, value);
+        }
+        Self::Object(object)
+    }
+}
+
 impl JsonTypeToString for JsonValue {
     fn to_json_string(&self) -> String {
         self.to_string()
@@ -57,9 +104,10 @@ impl JsonType for JsonValue {
             // The ugly conversion here is needed because rust-json internally does not
             // distinguish integers from floats, which leads to have "1.2".as_i64() == 1
             |number| {
-                if number.fract() == 0.0 {
+                // 2^127 is the first integral value out of the i128 range
+                if number.fract() == 0.0 && number.abs() < 2_f64.powi(127) {
                     #[allow(clippy::cast_possible_truncation)]
-                    Some(i128::from(number.trunc() as i64))
+                    Some(number.trunc() as i128)
                 } else {
                     None
                 }
@@ -97,27 +145,116 @@ impl JsonType for JsonValue {
 
     #[must_use]
     fn get_attribute(&self, attribute_name: &str) -> Option<&Self> {
-        let extracted_value = self.index(attribute_name);
-        if let Self::Null = extracted_value {
-            None
+        // Indexing would return Null also for missing attributes, so explicitly null attributes would not be distinguishable
+        if let Self::Object(object) = self {
+            object.get(attribute_name)
         } else {
-            Some(extracted_value)
+            None
         }
     }
 
     #[must_use]
     fn get_index(&self, index: usize) -> Option<&Self> {
-        let extracted_value = self.index(index);
-        if let Self::Null = extracted_value {
-            None
+        // As for attributes, explicitly null items are returned (and not reported as missing)
+        if let Self::Array(array) = self {
+            array.get(index)
         } else {
-            Some(extracted_value)
+            None
         }
     }
 }
 
 impl ThreadSafeJsonType for JsonValue {}
 
+impl JsonTypeMut for JsonValue {
+    fn get_attribute_mut(&mut self, attribute_name: &str) -> Option<&mut Self> {
+        if let Self::Object(object) = self {
+            object.get_mut(attribute_name)
+        } else {
+            None
+        }
+    }
+
+    fn get_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        if let Self::Array(array) = self {
+            array.get_mut(index)
+        } else {
+            None
+        }
+    }
+
+    fn set_attribute(&mut self, attribute_name: &str, value: Self) -> Result<Option<Self>, Error> {
+        if let Self::Object(object) = self {
+            // json::object::Object::insert does not return the previous value, so we replace it in-place if present
+            if let Some(previous_value) = object.get_mut(attribute_name) {
+                Ok(Some(std::mem::replace(previous_value, value)))
+            } else {
+                object.insert(attribute_name, value);
+                Ok(None)
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn remove_attribute(&mut self, attribute_name: &str) -> Result<Option<Self>, Error> {
+        if let Self::Object(object) = self {
+            Ok(object.remove(attribute_name))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn push(&mut self, value: Self) -> Result<(), Error> {
+        if let Self::Array(array) = self {
+            array.push(value);
+            Ok(())
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn insert_index(&mut self, index: usize, value: Self) -> Result<(), Error> {
+        if let Self::Array(array) = self {
+            if index > array.len() {
+                Err(Error::IndexOutOfBounds { index, length: array.len() })
+            } else {
+                array.insert(index, value);
+                Ok(())
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn remove_index(&mut self, index: usize) -> Result<Self, Error> {
+        if let Self::Array(array) = self {
+            if index < array.len() {
+                Ok(array.remove(index))
+            } else {
+                Err(Error::IndexOutOfBounds { index, length: array.len() })
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 macro_rules! rust_json {
     ($($json:tt)+) => {{
@@ -182,12 +319,14 @@ mod tests_primitive_type_trait {
 
     #[test_case(&rust_json![{"present": 1}], "present", &Some(rust_json![1]))]
     #[test_case(&rust_json![{"present": 1}], "not-present", &None)]
+    #[test_case(&rust_json![{"present": null}], "present", &Some(rust_json![null]))]
     fn test_get_attribute(value: &JsonValue, attribute_name: &str, expected_value: &Option<JsonValue>) {
         assert_eq!(JsonType::get_attribute(value, attribute_name), expected_value.as_ref());
     }
 
     #[test_case(&rust_json![[0, 1, 2]], 1, &Some(rust_json![1]))]
     #[test_case(&rust_json![[0, 1, 2]], 4, &None)]
+    #[test_case(&rust_json![[0, null, 2]], 1, &Some(rust_json![null]))]
     fn test_get_index(value: &JsonValue, index: usize, expected_value: &Option<JsonValue>) {
         assert_eq!(JsonType::get_index(value, index), expected_value.as_ref());
     }
@@ -291,6 +430,7 @@ mod tests_primitive_type_trait {
     }
 
     #[test_case(&rust_json![1], Some(1))]
+    #[test_case(&rust_json![1e19], Some(10_000_000_000_000_000_000))]
     #[test_case(&rust_json![1.2], None)]
     #[test_case(&rust_json!["1"], None)]
     fn test_as_integer(value: &JsonValue, expected_value: Option<i128>) {
@@ -389,3 +529,55 @@ mod tests_to_json_string {
         );
     }
 }
+
+#[cfg(test)]
+mod tests_json_type_mut {
+    use crate::{
+        error::Error,
+        json_type::{JsonTypeMut, PrimitiveType},
+    };
+    use json::JsonValue;
+    use test_case::test_case;
+
+    #[test]
+    fn test_get_attribute_mut() {
+        let mut value = rust_json![{"key": [1]}];
+        *value.get_attribute_mut("key").unwrap().get_index_mut(0).unwrap() = rust_json!["changed"];
+        assert_eq!(value, rust_json![{"key": ["changed"]}]);
+    }
+
+    #[test_case(rust_json![{"key": 1}], "key" => Ok(Some(rust_json![1])))]
+    #[test_case(rust_json![{}], "key" => Ok(None))]
+    #[test_case(rust_json![[]], "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Array }))]
+    fn test_set_attribute(mut value: JsonValue, attribute_name: &str) -> Result<Option<JsonValue>, Error> {
+        let result = value.set_attribute(attribute_name, rust_json!["new"])?;
+        assert_eq!(value, rust_json![{"key": "new"}]);
+        Ok(result)
+    }
+
+    #[test_case(rust_json![{"key": 1}], "key" => Ok(Some(rust_json![1])))]
+    #[test_case(rust_json![1], "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Integer }))]
+    fn test_remove_attribute(mut value: JsonValue, attribute_name: &str) -> Result<Option<JsonValue>, Error> {
+        value.remove_attribute(attribute_name)
+    }
+
+    #[test_case(rust_json![[1, 2]], 1 => Ok(rust_json![[1, "new", 2]]))]
+    #[test_case(rust_json![[1, 2]], 3 => Err(Error::IndexOutOfBounds { index: 3, length: 2 }))]
+    fn test_insert_index(mut value: JsonValue, index: usize) -> Result<JsonValue, Error> {
+        value.insert_index(index, rust_json!["new"])?;
+        Ok(value)
+    }
+
+    #[test_case(rust_json![[1, 2]], 1 => Ok(rust_json![2]))]
+    #[test_case(rust_json![[1, 2]], 2 => Err(Error::IndexOutOfBounds { index: 2, length: 2 }))]
+    fn test_remove_index(mut value: JsonValue, index: usize) -> Result<JsonValue, Error> {
+        value.remove_index(index)
+    }
+
+    #[test_case(rust_json![[]] => Ok(rust_json![["new"]]))]
+    #[test_case(rust_json![null] => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::Null }))]
+    fn test_push(mut value: JsonValue) -> Result<JsonValue, Error> {
+        JsonTypeMut::push(&mut value, rust_json!["new"])?;
+        Ok(value)
+    }
+}
@@ -1,12 +1,11 @@
 use crate::{
-    json_type::{JsonMap, JsonMapTrait, JsonType, ToRustType},
+    error::Error,
+    json_type::{unexpected_primitive_type, JsonMap, JsonMapTrait, JsonType, PrimitiveType, ToRustType},
     rust_type_impl::RustType,
 };
-#[cfg(test)]
-use pyo3::Python;
 use pyo3::{
-    types::{PyAny, PyDict, PySequence},
-    PyTryInto,
+    types::{PyAny, PyDict, PyList, PyLong, PySequence, PyString},
+    PyErr, PyTryInto, Python, ToPyObject,
 };
 use std::{convert::TryInto, ops::Deref};
 
@@ -18,6 +17,15 @@ impl Into<RustType> for PyAny {
 
 impl ToRustType for PyAny {}
 
+impl From<PyErr> for Error {
+    fn from(error: PyErr) -> Self {
+        Self::ForeignException {
+            language: "Python",
+            message: error.to_string(),
+        }
+    }
+}
+
 impl<'json> JsonMapTrait<'json, PyAny> for JsonMap<'json, PyAny> {
     #[must_use]
     fn keys(&'json self) -> Box<dyn Iterator<Item = &str> + 'json> {
@@ -140,6 +148,120 @@ impl JsonType for PyAny {
         }
         None
     }
+
+    // The fallible accessors report the python exceptions raised while accessing values of the expected type,
+    // instead of reporting them as values of an unexpected primitive type (as the `as_*` accessors do)
+    fn try_as_array<'json>(&'json self) -> Result<Box<dyn ExactSizeIterator<Item = &Self> + 'json>, Error> {
+        if self.is_string() {
+            return Err(unexpected_primitive_type(self, PrimitiveType::Array));
+        }
+        match PyTryInto::<PySequence>::try_into(self) {
+            Ok(py_sequence) => Ok(Box::new(py_sequence.iter()?.collect::<Result<Vec<_>, _>>()?.into_iter())),
+            Err(_) => Err(unexpected_primitive_type(self, PrimitiveType::Array)),
+        }
+    }
+
+    fn try_as_integer(&self) -> Result<i128, Error> {
+        // Python integers are unbounded, so the extraction raises `OverflowError` for integers not fitting an i128
+        if self.is_boolean() || PyTryInto::<PyLong>::try_into(self).is_err() {
+            Err(unexpected_primitive_type(self, PrimitiveType::Integer))
+        } else {
+            Ok(self.extract()?)
+        }
+    }
+
+    fn try_as_string(&self) -> Result<&str, Error> {
+        // Python strings could contain lone surrogates, which cannot be encoded as UTF-8
+        if PyTryInto::<PyString>::try_into(self).is_err() {
+            Err(unexpected_primitive_type(self, PrimitiveType::String))
+        } else {
+            Ok(self.extract()?)
+        }
+    }
+}
+
+// NOTE: JsonTypeMut is not implemented for PyAny as python objects are only accessible via shared references (`&PyAny`)
+// and they cannot be moved in or out of the python interpreter. In-place edits should rely on pyo3 APIs (ie. `PyDict::set_item`).
+
+// Python counterpart of `JsonTypeBuilder`.
+// PyAny cannot implement `JsonTypeBuilder` as python objects can only be created while holding the GIL, so the
+// builder carries the `Python` token and all the created objects are bound to its lifetime.
+#[derive(Clone, Copy, Debug)]
+pub struct PyJsonBuilder<'py> {
+    py: Python<'py>,
+}
+
+impl<'py> PyJsonBuilder<'py> {
+    #[must_use]
+    pub const fn new(py: Python<'py>) -> Self {
+        Self { py }
+    }
+
+    #[must_use]
+    pub fn null(&self) -> &'py PyAny {
+        self.py.None().into_ref(self.py)
+    }
+
+    #[must_use]
+    pub fn from_bool(&self, value: bool) -> &'py PyAny {
+        value.to_object(self.py).into_ref(self.py)
+    }
+
+    #[must_use]
+    pub fn from_i128(&self, value: i128) -> &'py PyAny {
+        value.to_object(self.py).into_ref(self.py)
+    }
+
+    #[must_use]
+    pub fn from_f64(&self, value: f64) -> &'py PyAny {
+        value.to_object(self.py).into_ref(self.py)
+    }
+
+    #[must_use]
+    pub fn from_string(&self, value: &str) -> &'py PyAny {
+        value.to_object(self.py).into_ref(self.py)
+    }
+
+    #[must_use]
+    pub fn from_array<I: IntoIterator<Item = &'py PyAny>>(&self, items: I) -> &'py PyAny {
+        PyList::new(self.py, items.into_iter().collect::<Vec<_>>()).as_ref()
+    }
+
+    #[must_use]
+    pub fn from_object<I: IntoIterator<Item = (String, &'py PyAny)>>(&self, items: I) -> &'py PyAny {
+        let python_dict = PyDict::new(self.py);
+        for (key, value) in items {
+            // Setting a string key on a dictionary cannot fail (string keys are always hashable)
+            let _ = python_dict.set_item(key, value);
+        }
+        python_dict.as_ref()
+    }
+
+    /// Build the python representation of any JsonType instance (without using RustType as intermediate representation)
+    ///
+    /// # Errors
+    /// `Error::UnrecognizedValue` if `value`, or any of its descendants, is not recognized as a JSON primitive type.
+    pub fn convert<T: JsonType>(&self, value: &T) -> Result<&'py PyAny, Error> {
+        Ok(match value.try_primitive_type()? {
+            PrimitiveType::Array => self.from_array(value.as_array().into_iter().flatten().map(|item| self.convert(item)).collect::<Result<Vec<_>, _>>()?),
+            PrimitiveType::Boolean => self.from_bool(value.as_boolean().unwrap_or_default()),
+            PrimitiveType::Integer => self.from_i128(value.as_integer().unwrap_or_default()),
+            PrimitiveType::Null => self.null(),
+            PrimitiveType::Number => self.from_f64(value.as_number().unwrap_or_default()),
+            PrimitiveType::Object => {
+                let keys = value
+                    .as_object()
+                    .map(|object| object.keys().map(ToString::to_string).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                self.from_object(
+                    keys.into_iter()
+                        .filter_map(|key| value.get_attribute(&key).map(|attribute_value| Ok((key, self.convert(attribute_value)?))))
+                        .collect::<Result<Vec<_>, Error>>()?,
+                )
+            }
+            PrimitiveType::String => self.from_string(value.as_string().unwrap_or_default()),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -199,7 +321,10 @@ mod tests_json_map_trait {
 #[cfg(test)]
 mod tests_primitive_type_trait {
     use super::perform_python_check;
-    use crate::json_type::{JsonType, PrimitiveType};
+    use crate::{
+        error::Error,
+        json_type::{JsonType, PrimitiveType},
+    };
     use test_case::test_case;
 
     #[test_case("[]", PrimitiveType::Array)]
@@ -369,6 +494,32 @@ mod tests_primitive_type_trait {
     fn test_as_string(python_code_string: &str, expected_value: Option<&str>) {
         perform_python_check(python_code_string, |python_object_ref| assert_eq!(JsonType::as_string(python_object_ref), expected_value))
     }
+
+    #[test_case("10 ** 40", "integer" ; "integer overflowing i128")]
+    #[test_case("'\\ud800'", "string" ; "string with lone surrogate")]
+    fn test_python_exceptions_are_reported(python_code_string: &str, accessor: &str) {
+        perform_python_check(python_code_string, |python_object_ref| {
+            let result = match accessor {
+                "integer" => JsonType::try_as_integer(python_object_ref).map(|_| ()),
+                _ => JsonType::try_as_string(python_object_ref).map(|_| ()),
+            };
+            assert!(matches!(result, Err(Error::ForeignException { language: "Python", .. })), "{:?}", result);
+        })
+    }
+
+    #[test_case("1", PrimitiveType::Integer ; "integer")]
+    #[test_case("'1'", PrimitiveType::String ; "string")]
+    fn test_unexpected_primitive_types_are_reported(python_code_string: &str, actual: PrimitiveType) {
+        perform_python_check(python_code_string, |python_object_ref| {
+            assert_eq!(
+                JsonType::try_as_array(python_object_ref).map(|_| ()),
+                Err(Error::UnexpectedPrimitiveType {
+                    expected: PrimitiveType::Array,
+                    actual
+                })
+            );
+        })
+    }
 }
 
 #[cfg(test)]
@@ -435,3 +586,19 @@ mod tests_to_json_string {
         });
     }
 }
+
+#[cfg(test)]
+mod tests_py_json_builder {
+    use super::PyJsonBuilder;
+    use crate::json_type::{JsonType, ToRustType};
+    use pyo3::Python;
+
+    #[test]
+    fn test_convert() {
+        let gil = Python::acquire_gil();
+        let value = rust_type!({"array": [1, 2.5, null], "boolean": true, "object": {"string": "value"}});
+        let python_object = PyJsonBuilder::new(gil.python()).convert(&value).unwrap();
+        assert!(python_object.is_object());
+        assert_eq!(python_object.to_rust_type(), value);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
-    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeToString, ThreadSafeJsonType, ToRustType},
+    convert::{from_i128_with_float_fallback, try_from_i128_without_fallback},
+    error::Error,
+    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeBuilder, JsonTypeMut, JsonTypeToString, PrimitiveType, ThreadSafeJsonType, ToRustType},
     rust_type_impl::RustType,
 };
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 impl Into<RustType> for Value {
     fn into(self) -> RustType {
@@ -10,8 +12,65 @@ impl Into<RustType> for Value {
     }
 }
 
+impl From<RustType> for Value {
+    fn from(value: RustType) -> Self {
+        match value {
+            RustType::Null => Self::Null,
+            RustType::Boolean(value) => Self::Bool(value),
+            RustType::String(value) => Self::String(value),
+            RustType::Integer(value) => Self::from_i128(value),
+            RustType::Number(value) => Self::from_f64(value),
+            RustType::List(value) => Self::Array(value.into_iter().map(Self::from).collect()),
+            RustType::Object(value) => Self::Object(value.into_iter().map(|(key, value)| (key, Self::from(value))).collect::<Map<_, _>>()),
+        }
+    }
+}
+
 impl ToRustType for Value {}
 
+impl JsonTypeBuilder for Value {
+    fn null() -> Self {
+        Self::Null
+    }
+
+    fn from_bool(value: bool) -> Self {
+        Self::Bool(value)
+    }
+
+    fn from_i128(value: i128) -> Self {
+        from_i128_with_float_fallback(value)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        // NOTE: serde_json represents non finite numbers (NaN and infinities) as null
+        Self::from(value)
+    }
+
+    fn try_from_i128(value: i128) -> Option<Self> {
+        try_from_i128_without_fallback(value)
+    }
+
+    fn try_from_f64(value: f64) -> Option<Self> {
+        if value.is_finite() {
+            Some(Self::from(value))
+        } else {
+            None
+        }
+    }
+
+    fn from_string(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+
+    fn from_array<I: IntoIterator<Item = Self>>(items: I) -> Self {
+        Self::Array(items.into_iter().collect())
+    }
+
+    fn from_object<I: IntoIterator<Item = (String, Self)>>(items: I) -> Self {
+        Self::Object(items.into_iter().collect::<Map<_, _>>())
+    }
+}
+
 impl JsonTypeToString for Value {
     fn to_json_string(&self) -> String {
         self.to_string()
@@ -24,10 +83,8 @@ impl<'json> JsonMapTrait<'json, Value> for JsonMap<'json, Value> {
         if let Some(obj) = self.as_object() {
             Box::new(obj.keys().map(AsRef::as_ref))
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            // `JsonMap` wrapping a value that is not an object (ie. via `JsonMap::new`) has no entries
+            Box::new(std::iter::empty())
         }
     }
 
@@ -36,10 +93,7 @@ impl<'json> JsonMapTrait<'json, Value> for JsonMap<'json, Value> {
         if let Some(obj) = self.as_object() {
             Box::new(obj.values())
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            Box::new(std::iter::empty())
         }
     }
 
@@ -48,10 +102,7 @@ impl<'json> JsonMapTrait<'json, Value> for JsonMap<'json, Value> {
         if let Some(obj) = self.as_object() {
             Box::new(obj.iter().map(|(k, v)| (k.as_ref(), v)))
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            Box::new(std::iter::empty())
         }
     }
 }
@@ -73,11 +124,8 @@ impl JsonType for Value {
 
     #[must_use]
     fn as_integer(&self) -> Option<i128> {
-        if let Some(value) = self.as_i64() {
-            Some(i128::from(value))
-        } else {
-            None
-        }
+        // Integers above i64::MAX are represented as u64
+        self.as_i64().map(i128::from).or_else(|| self.as_u64().map(i128::from))
     }
 
     #[must_use]
@@ -122,6 +170,81 @@ impl JsonType for Value {
 
 impl ThreadSafeJsonType for Value {}
 
+impl JsonTypeMut for Value {
+    fn get_attribute_mut(&mut self, attribute_name: &str) -> Option<&mut Self> {
+        self.as_object_mut().and_then(|object| object.get_mut(attribute_name))
+    }
+
+    fn get_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        self.as_array_mut().and_then(|array| array.get_mut(index))
+    }
+
+    fn set_attribute(&mut self, attribute_name: &str, value: Self) -> Result<Option<Self>, Error> {
+        if let Self::Object(object) = self {
+            Ok(object.insert(attribute_name.to_string(), value))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn remove_attribute(&mut self, attribute_name: &str) -> Result<Option<Self>, Error> {
+        if let Self::Object(object) = self {
+            Ok(object.remove(attribute_name))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn push(&mut self, value: Self) -> Result<(), Error> {
+        if let Self::Array(array) = self {
+            array.push(value);
+            Ok(())
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn insert_index(&mut self, index: usize, value: Self) -> Result<(), Error> {
+        if let Self::Array(array) = self {
+            if index > array.len() {
+                Err(Error::IndexOutOfBounds { index, length: array.len() })
+            } else {
+                array.insert(index, value);
+                Ok(())
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn remove_index(&mut self, index: usize) -> Result<Self, Error> {
+        if let Self::Array(array) = self {
+            if index < array.len() {
+                Ok(array.remove(index))
+            } else {
+                Err(Error::IndexOutOfBounds { index, length: array.len() })
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests_json_map_trait {
     use crate::json_type::{JsonMap, JsonMapTrait};
@@ -148,6 +271,13 @@ mod tests_json_map_trait {
         let testing_map: &Value = &TESTING_MAP;
         assert_eq!(JsonMap::new(testing_map).items().collect::<Vec<_>>(), vec![("k1", &json!["v1"]), ("k2", &json!["v2"])]);
     }
+
+    #[test]
+    fn not_an_object() {
+        let value = json![[1]];
+        let json_map = JsonMap::new(&value);
+        assert_eq!((json_map.keys().count(), json_map.values().count(), json_map.items().count()), (0, 0, 0));
+    }
 }
 
 #[cfg(test)]
@@ -377,3 +507,76 @@ mod tests_to_json_string {
         );
     }
 }
+
+#[cfg(test)]
+mod tests_from_rust_type {
+    use crate::rust_type_impl::RustType;
+    use serde_json::Value;
+    use test_case::test_case;
+
+    #[test_case(rust_type!(null) => json![null])]
+    #[test_case(rust_type!(true) => json![true])]
+    #[test_case(rust_type!(1) => json![1])]
+    #[test_case(RustType::Integer(i128::from(u64::MAX)) => json![u64::MAX])]
+    #[test_case(RustType::Integer(i128::MAX) => json![1.701_411_834_604_692_3e38])]
+    #[test_case(rust_type!(1.5) => json![1.5])]
+    #[test_case(rust_type!(f64::NAN) => json![null])]
+    #[test_case(rust_type!("string") => json!["string"])]
+    #[test_case(rust_type!([1, [null]]) => json![[1, [null]]])]
+    #[test_case(rust_type!({"key": {"inner_key": [1]}}) => json![{"key": {"inner_key": [1]}}])]
+    fn test_from_rust_type(value: RustType) -> Value {
+        Value::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests_json_type_mut {
+    use crate::{
+        error::Error,
+        json_type::{JsonTypeMut, PrimitiveType},
+    };
+    use serde_json::Value;
+    use test_case::test_case;
+
+    #[test]
+    fn test_get_attribute_mut() {
+        let mut value = json![{"key": [1]}];
+        *JsonTypeMut::get_attribute_mut(&mut value, "key").unwrap().get_index_mut(0).unwrap() = json!["changed"];
+        assert_eq!(value, json![{"key": ["changed"]}]);
+    }
+
+    #[test_case(json![{"key": 1}], "key" => Ok(Some(json![1])))]
+    #[test_case(json![{}], "key" => Ok(None))]
+    #[test_case(json![[]], "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Array }))]
+    fn test_set_attribute(mut value: Value, attribute_name: &str) -> Result<Option<Value>, Error> {
+        let result = value.set_attribute(attribute_name, json!["new"])?;
+        assert_eq!(value, json![{"key": "new"}]);
+        Ok(result)
+    }
+
+    #[test_case(json![{"key": 1}], "key" => Ok(Some(json![1])))]
+    #[test_case(json![1], "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Integer }))]
+    fn test_remove_attribute(mut value: Value, attribute_name: &str) -> Result<Option<Value>, Error> {
+        value.remove_attribute(attribute_name)
+    }
+
+    #[test_case(json![[1, 2]], 1 => Ok(json![[1, "new", 2]]))]
+    #[test_case(json![[1, 2]], 3 => Err(Error::IndexOutOfBounds { index: 3, length: 2 }))]
+    fn test_insert_index(mut value: Value, index: usize) -> Result<Value, Error> {
+        value.insert_index(index, json!["new"])?;
+        Ok(value)
+    }
+
+    #[test_case(json![[1, 2]], 1 => Ok(json![2]))]
+    #[test_case(json![[1, 2]], 2 => Err(Error::IndexOutOfBounds { index: 2, length: 2 }))]
+    fn test_remove_index(mut value: Value, index: usize) -> Result<Value, Error> {
+        value.remove_index(index)
+    }
+
+    #[test_case(json![[]] => Ok(json![["new"]]))]
+    #[test_case(json![null] => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::Null }))]
+    fn test_push(mut value: Value) -> Result<Value, Error> {
+        value.push(json!["new"])?;
+        Ok(value)
+    }
+}
@@ -1,8 +1,46 @@
 use crate::{
-    json_type::{JsonMap, JsonMapTrait, JsonType, ThreadSafeJsonType, ToRustType},
+    convert::{convert_into, from_i128_with_float_fallback, try_from_i128_without_fallback, NonStringKeyPolicy},
+    error::Error,
+    json_type::{JsonMap, JsonMapTrait, JsonType, JsonTypeBuilder, JsonTypeMut, PrimitiveType, ThreadSafeJsonType, ToRustType},
     rust_type_impl::RustType,
 };
-use serde_yaml::Value;
+use indexmap::IndexMap;
+use serde_yaml::{Mapping, Value};
+use std::cell::Cell;
+
+thread_local! {
+    // Policy applied to the non-string keys by `JsonType::get_attribute` and `ToRustType::try_to_rust_type`
+    static NON_STRING_KEY_POLICY: Cell<NonStringKeyPolicy> = const { Cell::new(NonStringKeyPolicy::Stringify) };
+}
+
+// Restores the previous policy once `with_non_string_key_policy` completes, even if it panics
+struct NonStringKeyPolicyGuard(NonStringKeyPolicy);
+
+impl Drop for NonStringKeyPolicyGuard {
+    fn drop(&mut self) {
+        NON_STRING_KEY_POLICY.with(|policy| policy.set(self.0));
+    }
+}
+
+/// Run `f` reading the non-string keys of the mappings (ie. `{200: OK}`) according to `policy`.
+///
+/// By default (`NonStringKeyPolicy::Stringify`) scalar keys are reachable via `JsonType::get_attribute` and are
+/// reported by `ToRustType::to_rust_type` with their string representation (`200` -> `"200"`), sequence and mapping
+/// keys have no string representation and are skipped.
+/// With `NonStringKeyPolicy::Skip` non-string keys are ignored and with `NonStringKeyPolicy::Error` they are ignored
+/// by `get_attribute` and reported as `Error::NonStringKey` by `try_to_rust_type`.
+///
+/// NOTE: `JsonMapTrait` exposes borrowed `&str` keys, so non-string keys are never reported by `keys` and `items`.
+/// Use `normalize_keys` to make them visible to the generic `JsonType` consumers.
+/// The policy applies to the current thread only.
+pub fn with_non_string_key_policy<R>(policy: NonStringKeyPolicy, f: impl FnOnce() -> R) -> R {
+    let _guard = NonStringKeyPolicyGuard(NON_STRING_KEY_POLICY.with(|current_policy| current_policy.replace(policy)));
+    f()
+}
+
+fn non_string_key_policy() -> NonStringKeyPolicy {
+    NON_STRING_KEY_POLICY.with(Cell::get)
+}
 
 impl Into<RustType> for Value {
     fn into(self) -> RustType {
@@ -10,42 +48,160 @@ impl Into<RustType> for Value {
     }
 }
 
-impl ToRustType for Value {}
+impl ToRustType for Value {
+    fn try_to_rust_type(&self) -> Result<RustType, Error> {
+        let policy = non_string_key_policy();
+        match self {
+            Self::Mapping(mapping) => {
+                let mut object = IndexMap::new();
+                for (key, value) in mapping {
+                    if let Some(string_key) = read_key(key, policy)? {
+                        let value = value.try_to_rust_type()?;
+                        // String keys take precedence over colliding stringified keys, as for `get_attribute`
+                        if key.is_string() {
+                            let _ = object.insert(string_key, value);
+                        } else {
+                            let _ = object.entry(string_key).or_insert(value);
+                        }
+                    }
+                }
+                Ok(RustType::Object(object))
+            }
+            Self::Sequence(items) => Ok(RustType::from(items.iter().map(ToRustType::try_to_rust_type).collect::<Result<Vec<_>, _>>()?)),
+            _ => convert_into(self),
+        }
+    }
+}
+
+// String representation of a mapping key, scalar keys are stringified as YAML to JSON tools usually do (ie. `1` -> `"1"`).
+// None is returned for keys that do not have a reasonable string representation (sequences and mappings)
+fn stringify_key(key: &Value) -> Option<String> {
+    match key {
+        Value::Null => Some("null".to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::String(value) => Some(value.clone()),
+        Value::Sequence(_) | Value::Mapping(_) => None,
+        Value::Tagged(tagged_value) => stringify_key(&tagged_value.value),
+    }
+}
+
+// Key exposed for `key` according to `policy`, None if the key has to be skipped
+pub(crate) fn string_key(key: &Value, policy: NonStringKeyPolicy) -> Result<Option<String>, Error> {
+    if let Some(key) = key.as_str() {
+        return Ok(Some(key.to_string()));
+    }
+    match (policy, stringify_key(key)) {
+        (NonStringKeyPolicy::Skip, _) => Ok(None),
+        (NonStringKeyPolicy::Stringify, Some(key)) => Ok(Some(key)),
+        (_, key_string) => Err(Error::NonStringKey {
+            key: key_string.unwrap_or_else(|| format!("{key:?}")),
+        }),
+    }
+}
+
+// Key exposed by the read path (`get_attribute` and `try_to_rust_type`) for `key`, None if the key has to be skipped.
+// Sequence and mapping keys are skipped while stringifying, as `get_attribute` could not reach them anyway
+fn read_key(key: &Value, policy: NonStringKeyPolicy) -> Result<Option<String>, Error> {
+    if policy == NonStringKeyPolicy::Stringify && !key.is_string() && stringify_key(key).is_none() {
+        Ok(None)
+    } else {
+        string_key(key, policy)
+    }
+}
+
+/// Rewrite the non-string keys of all the nested mappings according to `policy`
+///
+/// YAML mappings allow non-string keys (ie. `{200: OK}`) that `JsonMapTrait` cannot expose, as keys are borrowed `&str`.
+/// Without normalization such keys, and their values, are skipped by `JsonType::as_object` (and so by the generic
+/// consumers like `convert` or `json_eq`) even if `get_attribute` and `to_rust_type` can reach them (see `with_non_string_key_policy`).
+/// If stringified keys collide with existing ones the latest value wins.
+///
+/// # Errors
+/// `Error::NonStringKey` is returned for `NonStringKeyPolicy::Error`, or for sequence and mapping keys with
+/// `NonStringKeyPolicy::Stringify`. The failing mapping is left untouched, but previously visited mappings might
+/// have been normalized already.
+pub fn normalize_keys(value: &mut Value, policy: NonStringKeyPolicy) -> Result<(), Error> {
+    match value {
+        Value::Mapping(mapping) => {
+            if mapping.keys().any(|key| !key.is_string()) {
+                let keys = mapping.keys().map(|key| string_key(key, policy)).collect::<Result<Vec<_>, _>>()?;
+                let entries = std::mem::take(mapping);
+                for (key, (_, value)) in keys.into_iter().zip(entries) {
+                    if let Some(key) = key {
+                        let _ = mapping.insert(Value::String(key), value);
+                    }
+                }
+            }
+            mapping.iter_mut().try_for_each(|(_, value)| normalize_keys(value, policy))
+        }
+        Value::Sequence(sequence) => sequence.iter_mut().try_for_each(|item| normalize_keys(item, policy)),
+        Value::Tagged(tagged_value) => normalize_keys(&mut tagged_value.value, policy),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => Ok(()),
+    }
+}
+
+impl JsonTypeBuilder for Value {
+    fn null() -> Self {
+        Self::Null
+    }
+
+    fn from_bool(value: bool) -> Self {
+        Self::Bool(value)
+    }
+
+    fn from_i128(value: i128) -> Self {
+        from_i128_with_float_fallback(value)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        Self::from(value)
+    }
+
+    fn try_from_i128(value: i128) -> Option<Self> {
+        try_from_i128_without_fallback(value)
+    }
+
+    fn from_string(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+
+    fn from_array<I: IntoIterator<Item = Self>>(items: I) -> Self {
+        Self::Sequence(items.into_iter().collect())
+    }
+
+    fn from_object<I: IntoIterator<Item = (String, Self)>>(items: I) -> Self {
+        Self::Mapping(items.into_iter().map(|(key, value)| (Self::String(key), value)).collect::<Mapping>())
+    }
+}
 
 impl<'json> JsonMapTrait<'json, Value> for JsonMap<'json, Value> {
     #[must_use]
     fn keys(&'json self) -> Box<dyn Iterator<Item = &str> + 'json> {
         if let Some(obj) = self.as_mapping() {
-            Box::new(obj.iter().map(|(key, _)| (key.as_str().unwrap())))
+            // Non-string keys cannot be exposed as `&str`, use `normalize_keys` to make them visible
+            Box::new(obj.keys().filter_map(Value::as_str))
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            // `JsonMap` wrapping a value that is not an object (ie. via `JsonMap::new`) has no entries
+            Box::new(std::iter::empty())
         }
     }
 
     #[must_use]
     fn values(&'json self) -> Box<dyn Iterator<Item = &Value> + 'json> {
         if let Some(obj) = self.as_mapping() {
-            Box::new(obj.iter().map(|(_, value)| value))
+            Box::new(obj.iter().filter_map(|(key, value)| key.as_str().map(|_| value)))
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            Box::new(std::iter::empty())
         }
     }
 
     #[must_use]
     fn items(&'json self) -> Box<dyn Iterator<Item = (&str, &Value)> + 'json> {
         if let Some(obj) = self.as_mapping() {
-            Box::new(obj.iter().map(|(key, value)| (key.as_str().unwrap(), value)))
+            Box::new(obj.iter().filter_map(|(key, value)| key.as_str().map(|key| (key, value))))
         } else {
-            #[allow(unsafe_code)]
-            unsafe {
-                std::hint::unreachable_unchecked()
-            }
+            Box::new(std::iter::empty())
         }
     }
 }
@@ -67,11 +223,8 @@ impl JsonType for Value {
 
     #[must_use]
     fn as_integer(&self) -> Option<i128> {
-        if let Some(value) = self.as_i64() {
-            Some(i128::from(value))
-        } else {
-            None
-        }
+        // Integers above i64::MAX are represented as u64
+        self.as_i64().map(i128::from).or_else(|| self.as_u64().map(i128::from))
     }
 
     #[must_use]
@@ -100,7 +253,16 @@ impl JsonType for Value {
 
     #[must_use]
     fn get_attribute(&self, attribute_name: &str) -> Option<&Self> {
-        self.get(attribute_name)
+        self.get(attribute_name).or_else(|| {
+            if non_string_key_policy() == NonStringKeyPolicy::Stringify {
+                self.as_mapping()?
+                    .iter()
+                    .find(|(key, _)| !key.is_string() && stringify_key(key).as_deref() == Some(attribute_name))
+                    .map(|(_, value)| value)
+            } else {
+                None
+            }
+        })
     }
 
     #[must_use]
@@ -110,12 +272,88 @@ impl JsonType for Value {
 
     #[must_use]
     fn has_attribute(&self, attribute_name: &str) -> bool {
-        self.get(attribute_name).is_some()
+        JsonType::get_attribute(self, attribute_name).is_some()
     }
 }
 
 impl ThreadSafeJsonType for Value {}
 
+impl JsonTypeMut for Value {
+    fn get_attribute_mut(&mut self, attribute_name: &str) -> Option<&mut Self> {
+        self.as_mapping_mut().and_then(|mapping| mapping.get_mut(attribute_name))
+    }
+
+    fn get_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        self.as_sequence_mut().and_then(|sequence| sequence.get_mut(index))
+    }
+
+    fn set_attribute(&mut self, attribute_name: &str, value: Self) -> Result<Option<Self>, Error> {
+        if let Self::Mapping(mapping) = self {
+            Ok(mapping.insert(Self::String(attribute_name.to_string()), value))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn remove_attribute(&mut self, attribute_name: &str) -> Result<Option<Self>, Error> {
+        if let Self::Mapping(mapping) = self {
+            // shift_remove preserves the order of the remaining keys
+            Ok(mapping.shift_remove(attribute_name))
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Object,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn push(&mut self, value: Self) -> Result<(), Error> {
+        if let Self::Sequence(sequence) = self {
+            sequence.push(value);
+            Ok(())
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn insert_index(&mut self, index: usize, value: Self) -> Result<(), Error> {
+        if let Self::Sequence(sequence) = self {
+            if index > sequence.len() {
+                Err(Error::IndexOutOfBounds { index, length: sequence.len() })
+            } else {
+                sequence.insert(index, value);
+                Ok(())
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+
+    fn remove_index(&mut self, index: usize) -> Result<Self, Error> {
+        if let Self::Sequence(sequence) = self {
+            if index < sequence.len() {
+                Ok(sequence.remove(index))
+            } else {
+                Err(Error::IndexOutOfBounds { index, length: sequence.len() })
+            }
+        } else {
+            Err(Error::UnexpectedPrimitiveType {
+                expected: PrimitiveType::Array,
+                actual: JsonType::primitive_type(self),
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 macro_rules! yaml {
     ($($json:tt)+) => {{
@@ -155,6 +393,13 @@ mod tests_yaml_map_trait {
         let testing_map: &Value = &TESTING_MAP;
         assert_eq!(JsonMap::new(testing_map).items().collect::<Vec<_>>(), vec![("k1", &yaml!["v1"]), ("k2", &yaml!["v2"])]);
     }
+
+    #[test]
+    fn not_an_object() {
+        let value = yaml![[1]];
+        let json_map = JsonMap::new(&value);
+        assert_eq!((json_map.keys().count(), json_map.values().count(), json_map.items().count()), (0, 0, 0));
+    }
 }
 
 #[cfg(test)]
@@ -365,7 +610,7 @@ mod tests_json_map {
 
 #[cfg(test)]
 mod tests_to_json_string {
-    use crate::json_type::JsonTypeToString;
+    use crate::json_type::{JsonTypeToString, ToRustType};
 
     #[test]
     fn smoke_test() {
@@ -383,4 +628,172 @@ mod tests_to_json_string {
             r#"[{"array":[]},{"boolean":false},{"float":2.3},{"integer":1},{"null":null},{"object":{}},{"string":"string"}]"#
         );
     }
+
+    #[test]
+    fn test_attributes_order_is_preserved() {
+        let value = serde_yaml::from_str::<serde_yaml::Value>("z: 1\na:\n  y: [2]\n  b: 3\n").unwrap();
+        let expected = r#"{"z":1,"a":{"y":[2],"b":3}}"#;
+        assert_eq!(value.to_json_string(), expected);
+        assert_eq!(value.to_rust_type().to_json_string(), expected);
+    }
+
+    #[test]
+    fn test_escaping_and_non_finite_numbers() {
+        let value = yaml![{"quote\"": ["new\nline", f64::NAN]}];
+        assert_eq!(value.to_json_string(), r#"{"quote\"":["new\nline",null]}"#);
+    }
+}
+
+#[cfg(test)]
+mod tests_json_type_mut {
+    use crate::{
+        error::Error,
+        json_type::{JsonTypeMut, PrimitiveType},
+    };
+    use serde_yaml::Value;
+    use test_case::test_case;
+
+    #[test]
+    fn test_get_attribute_mut() {
+        let mut value = yaml![{"key": [1]}];
+        *JsonTypeMut::get_attribute_mut(&mut value, "key").unwrap().get_index_mut(0).unwrap() = yaml!["changed"];
+        assert_eq!(value, yaml![{"key": ["changed"]}]);
+    }
+
+    #[test_case(yaml![{"key": 1}], "key" => Ok(Some(yaml![1])))]
+    #[test_case(yaml![{}], "key" => Ok(None))]
+    #[test_case(yaml![[]], "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Array }))]
+    fn test_set_attribute(mut value: Value, attribute_name: &str) -> Result<Option<Value>, Error> {
+        let result = value.set_attribute(attribute_name, yaml!["new"])?;
+        assert_eq!(value, yaml![{"key": "new"}]);
+        Ok(result)
+    }
+
+    #[test_case(yaml![{"key": 1}], "key" => Ok(Some(yaml![1])))]
+    #[test_case(yaml![1], "key" => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Object, actual: PrimitiveType::Integer }))]
+    fn test_remove_attribute(mut value: Value, attribute_name: &str) -> Result<Option<Value>, Error> {
+        value.remove_attribute(attribute_name)
+    }
+
+    #[test_case(yaml![[1, 2]], 1 => Ok(yaml![[1, "new", 2]]))]
+    #[test_case(yaml![[1, 2]], 3 => Err(Error::IndexOutOfBounds { index: 3, length: 2 }))]
+    fn test_insert_index(mut value: Value, index: usize) -> Result<Value, Error> {
+        value.insert_index(index, yaml!["new"])?;
+        Ok(value)
+    }
+
+    #[test_case(yaml![[1, 2]], 1 => Ok(yaml![2]))]
+    #[test_case(yaml![[1, 2]], 2 => Err(Error::IndexOutOfBounds { index: 2, length: 2 }))]
+    fn test_remove_index(mut value: Value, index: usize) -> Result<Value, Error> {
+        value.remove_index(index)
+    }
+
+    #[test_case(yaml![[]] => Ok(yaml![["new"]]))]
+    #[test_case(yaml![null] => Err(Error::UnexpectedPrimitiveType { expected: PrimitiveType::Array, actual: PrimitiveType::Null }))]
+    fn test_push(mut value: Value) -> Result<Value, Error> {
+        value.push(yaml!["new"])?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests_non_string_keys {
+    use super::{normalize_keys, with_non_string_key_policy};
+    use crate::{
+        convert::NonStringKeyPolicy,
+        error::Error,
+        json_type::{JsonMapTrait, JsonType, ToRustType},
+    };
+    use serde_yaml::Value;
+    use test_case::test_case;
+
+    const RESPONSES: &str = "200: {description: OK}\n404: {description: Not Found}\ndefault: {description: Error}\n";
+
+    #[test]
+    fn test_non_string_keys_are_not_exposed_by_json_map() {
+        let value = serde_yaml::from_str::<Value>("{1: a, true: b, x: c}").unwrap();
+        let object = JsonType::as_object(&value).unwrap();
+        assert_eq!(object.keys().collect::<Vec<_>>(), vec!["x"]);
+        assert_eq!(object.values().collect::<Vec<_>>(), vec![&Value::from("c")]);
+        assert_eq!(object.items().count(), 1);
+    }
+
+    #[test]
+    fn test_non_string_keys_are_stringified_by_default() {
+        let value = serde_yaml::from_str::<Value>(RESPONSES).unwrap();
+        assert_eq!(
+            value.to_rust_type(),
+            rust_type!({"200": {"description": "OK"}, "404": {"description": "Not Found"}, "default": {"description": "Error"}})
+        );
+        assert_eq!(
+            JsonType::get_attribute(&value, "404").and_then(|response| JsonType::get_attribute(response, "description")),
+            Some(&Value::from("Not Found"))
+        );
+        assert!(JsonType::has_attribute(&value, "200"));
+        assert!(!JsonType::has_attribute(&value, "500"));
+    }
+
+    #[test_case("{1: a, ~: b, [2]: c, x: {false: d}}", NonStringKeyPolicy::Stringify => Ok(rust_type!({"1": "a", "null": "b", "x": {"false": "d"}})) ; "stringify")]
+    #[test_case("{1: a, ~: b, [2]: c, x: {false: d}}", NonStringKeyPolicy::Skip => Ok(rust_type!({"x": {}})) ; "skip")]
+    #[test_case("{x: [{1: a}]}", NonStringKeyPolicy::Error => Err(Error::NonStringKey { key: "1".to_string() }) ; "error")]
+    #[test_case("{1: a, \"1\": b}", NonStringKeyPolicy::Stringify => Ok(rust_type!({"1": "b"})) ; "string key after colliding key")]
+    #[test_case("{\"1\": b, 1: a}", NonStringKeyPolicy::Stringify => Ok(rust_type!({"1": "b"})) ; "string key before colliding key")]
+    fn test_try_to_rust_type_with_policy(yaml: &str, policy: NonStringKeyPolicy) -> Result<crate::rust_type_impl::RustType, Error> {
+        let value = serde_yaml::from_str::<Value>(yaml).unwrap();
+        with_non_string_key_policy(policy, || value.try_to_rust_type())
+    }
+
+    #[test_case(NonStringKeyPolicy::Stringify => Some(Value::from("a")))]
+    #[test_case(NonStringKeyPolicy::Skip => None)]
+    #[test_case(NonStringKeyPolicy::Error => None)]
+    fn test_get_attribute_with_policy(policy: NonStringKeyPolicy) -> Option<Value> {
+        let value = serde_yaml::from_str::<Value>("{1: a, \"2\": b}").unwrap();
+        with_non_string_key_policy(policy, || {
+            assert_eq!(JsonType::get_attribute(&value, "2"), Some(&Value::from("b")));
+            JsonType::get_attribute(&value, "1").cloned()
+        })
+    }
+
+    #[test]
+    fn test_policy_is_restored() {
+        let value = serde_yaml::from_str::<Value>("{1: a}").unwrap();
+        assert_eq!(
+            with_non_string_key_policy(NonStringKeyPolicy::Error, || value.try_to_rust_type()).map(|_| ()),
+            Err(Error::NonStringKey { key: "1".to_string() })
+        );
+        assert_eq!(value.try_to_rust_type(), Ok(rust_type!({"1": "a"})));
+    }
+
+    #[test]
+    fn test_stringify_http_status_codes() {
+        let mut value = serde_yaml::from_str::<Value>(RESPONSES).unwrap();
+        normalize_keys(&mut value, NonStringKeyPolicy::Stringify).unwrap();
+        assert_eq!(JsonType::as_object(&value).unwrap().keys().collect::<Vec<_>>(), vec!["200", "404", "default"]);
+        assert_eq!(
+            JsonType::get_attribute(&value, "404").and_then(|response| JsonType::get_attribute(response, "description")),
+            Some(&Value::from("Not Found"))
+        );
+    }
+
+    #[test_case("{1: a, ~: b, x: {false: c}}", NonStringKeyPolicy::Stringify => Ok(rust_type!({"1": "a", "null": "b", "x": {"false": "c"}})) ; "stringify")]
+    #[test_case("{1: a, ~: b, x: {false: c}}", NonStringKeyPolicy::Skip => Ok(rust_type!({"x": {}})) ; "skip")]
+    #[test_case("{x: [{1: a}]}", NonStringKeyPolicy::Error => Err(Error::NonStringKey { key: "1".to_string() }) ; "error")]
+    #[test_case("{1: a, \"1\": b}", NonStringKeyPolicy::Stringify => Ok(rust_type!({"1": "b"})) ; "colliding keys")]
+    fn test_normalize_keys(yaml: &str, policy: NonStringKeyPolicy) -> Result<crate::rust_type_impl::RustType, Error> {
+        let mut value = serde_yaml::from_str::<Value>(yaml).unwrap();
+        normalize_keys(&mut value, policy).map(|()| value.to_rust_type())
+    }
+
+    #[test]
+    fn test_failing_mapping_is_left_untouched() {
+        let mut value = serde_yaml::from_str::<Value>("{[1]: a, 2: b}").unwrap();
+        let original = value.clone();
+        assert_eq!(
+            normalize_keys(&mut value, NonStringKeyPolicy::Stringify),
+            Err(Error::NonStringKey {
+                key: "Sequence [Number(1)]".to_string()
+            })
+        );
+        assert_eq!(value, original);
+    }
 }
@@ -0,0 +1,202 @@
+// Build a new JsonType (RustType by default) out of any JsonType applying user defined closures to each node.
+// This avoids the creation of an intermediate RustType (via `to_rust_type`) followed by a mutating pass.
+use crate::{
+    convert::convert,
+    cursor::PathComponent,
+    json_type::{JsonMapTrait, JsonType, JsonTypeBuilder, PrimitiveType},
+    rust_type_impl::RustType,
+};
+use indexmap::IndexMap;
+use std::fmt;
+
+type KeyClosure<'a> = Box<dyn FnMut(&[PathComponent], &str) -> Option<String> + 'a>;
+type ScalarClosure<'a, T, R> = Box<dyn FnMut(&[PathComponent], &T) -> Option<R> + 'a>;
+type ObjectClosure<'a, R> = Box<dyn FnMut(&[PathComponent], IndexMap<String, R>) -> Option<R> + 'a>;
+type ArrayClosure<'a, R> = Box<dyn FnMut(&[PathComponent], Vec<R>) -> Option<R> + 'a>;
+
+// All the closures receive the path of the node that is being transformed and returning None drops the node
+// from the containing object/array (or from the result if the node is the root).
+// Children are transformed before their container, so object and array closures receive already transformed values.
+// The output backend is defined by `R`, any JsonTypeBuilder implementation can be used.
+pub struct Transform<'a, T: JsonType, R: JsonTypeBuilder = RustType> {
+    key: KeyClosure<'a>,
+    scalar: ScalarClosure<'a, T, R>,
+    object: ObjectClosure<'a, R>,
+    array: ArrayClosure<'a, R>,
+}
+
+impl<T: JsonType, R: JsonTypeBuilder> fmt::Debug for Transform<'_, T, R> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("Transform").finish_non_exhaustive()
+    }
+}
+
+impl<T: JsonType, R: JsonTypeBuilder> Default for Transform<'_, T, R> {
+    fn default() -> Self {
+        Self {
+            key: Box::new(|_, key| Some(key.to_string())),
+            // Scalars reaching the closure are recognized values, so the conversion does not fail
+            scalar: Box::new(|_, value| convert(value).ok()),
+            object: Box::new(|_, object| Some(R::from_object(object))),
+            array: Box::new(|_, items| Some(R::from_array(items))),
+        }
+    }
+}
+
+impl<'a, T: JsonType, R: JsonTypeBuilder> Transform<'a, T, R> {
+    // Identity transformation, use the `on_*` methods to customise it
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Rename object attributes, the path references the attribute with its original name.
+    // If multiple attributes are renamed to the same key the last one wins.
+    #[must_use]
+    pub fn on_key(mut self, closure: impl FnMut(&[PathComponent], &str) -> Option<String> + 'a) -> Self {
+        self.key = Box::new(closure);
+        self
+    }
+
+    // Transform null, boolean, integer, number and string values
+    #[must_use]
+    pub fn on_scalar(mut self, closure: impl FnMut(&[PathComponent], &T) -> Option<R> + 'a) -> Self {
+        self.scalar = Box::new(closure);
+        self
+    }
+
+    #[must_use]
+    pub fn on_object(mut self, closure: impl FnMut(&[PathComponent], IndexMap<String, R>) -> Option<R> + 'a) -> Self {
+        self.object = Box::new(closure);
+        self
+    }
+
+    #[must_use]
+    pub fn on_array(mut self, closure: impl FnMut(&[PathComponent], Vec<R>) -> Option<R> + 'a) -> Self {
+        self.array = Box::new(closure);
+        self
+    }
+
+    fn transform_node(&mut self, value: &T, path: &mut Vec<PathComponent>) -> Option<R> {
+        match value.primitive_type() {
+            PrimitiveType::Object => {
+                let mut object = IndexMap::new();
+                if let Some(json_map) = value.as_object() {
+                    for key in json_map.keys() {
+                        path.push(PathComponent::Attribute(key.to_string()));
+                        let transformed = (self.key)(path, key).and_then(|new_key| {
+                            let attribute_value = value.get_attribute(key)?;
+                            self.transform_node(attribute_value, path).map(|new_value| (new_key, new_value))
+                        });
+                        let _ = path.pop();
+                        if let Some((new_key, new_value)) = transformed {
+                            let _ = object.insert(new_key, new_value);
+                        }
+                    }
+                }
+                (self.object)(path, object)
+            }
+            PrimitiveType::Array => {
+                let mut items = Vec::new();
+                if let Some(array) = value.as_array() {
+                    for (index, item) in array.enumerate() {
+                        path.push(PathComponent::Index(index));
+                        let transformed = self.transform_node(item, path);
+                        let _ = path.pop();
+                        items.extend(transformed);
+                    }
+                }
+                (self.array)(path, items)
+            }
+            _ => (self.scalar)(path, value),
+        }
+    }
+
+    /// Apply the transformation to `value`, None is returned if the root node has been dropped
+    ///
+    /// # Panics
+    /// The method panics if `value`, or any of its non dropped descendants, is not recognized as a JSON primitive type
+    pub fn apply(&mut self, value: &T) -> Option<R> {
+        self.transform_node(value, &mut Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform;
+    use crate::{cursor::PathComponent, json_type::JsonType, rust_type_impl::RustType};
+
+    lazy_static! {
+        static ref DOCUMENT: RustType = rust_type!({
+            "user": {"name": "John", "password": "secret", "age": 42},
+            "scores": [1, 2.5, null],
+        });
+    }
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(Transform::new().apply(&*DOCUMENT), Some(DOCUMENT.clone()));
+    }
+
+    #[test]
+    fn test_redact_and_rename() {
+        let result = Transform::new()
+            .on_key(|_, key| if key == "password" { None } else { Some(key.to_uppercase()) })
+            .on_scalar(|path, value: &RustType| {
+                if path.last() == Some(&PathComponent::Attribute("name".to_string())) {
+                    Some(rust_type!("<redacted>"))
+                } else {
+                    Some(value.clone())
+                }
+            })
+            .apply(&*DOCUMENT);
+        assert_eq!(
+            result,
+            Some(rust_type!({
+                "USER": {"NAME": "<redacted>", "AGE": 42},
+                "SCORES": [1, 2.5, null],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_coerce_and_drop_items() {
+        let result = Transform::new()
+            .on_scalar(|_, value: &RustType| {
+                if value.is_null() {
+                    None
+                } else if let Some(integer) = value.as_integer() {
+                    Some(rust_type!(integer.to_string()))
+                } else {
+                    Some(value.clone())
+                }
+            })
+            .on_array(|path, items| Some(rust_type!({"path": format!("{path:?}"), "items": RustType::from(items)})))
+            .apply(&rust_type!({"a": [1, null, 2]}));
+        assert_eq!(result, Some(rust_type!({"a": {"path": "[Attribute(\"a\")]", "items": ["1", "2"]}})));
+    }
+
+    #[test]
+    fn test_dropped_root() {
+        assert_eq!(Transform::<_, RustType>::new().on_object(|_, _| None).apply(&*DOCUMENT), None);
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_transform_serde_json() {
+        let result = Transform::new()
+            .on_scalar(|_, value: &serde_json::Value| value.as_string().map(|string| rust_type!(string.to_uppercase())))
+            .apply(&json!({"a": "abc", "b": [true, "de"]}));
+        assert_eq!(result, Some(rust_type!({"a": "ABC", "b": ["DE"]})));
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_transform_into_serde_json() {
+        let result = Transform::new()
+            .on_key(|_, key| Some(key.to_uppercase()))
+            .on_array(|_, items: Vec<serde_json::Value>| Some(serde_json::Value::from(items.len())))
+            .apply(&*DOCUMENT);
+        assert_eq!(result, Some(json!({"USER": {"NAME": "John", "PASSWORD": "secret", "AGE": 42}, "SCORES": 3})));
+    }
+}
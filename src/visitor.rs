@@ -0,0 +1,363 @@
+// Depth-first traversal of JsonType instances, either via callbacks (JsonVisitor + walk) or via iterator (iter_nodes)
+use crate::{
+    fragment_helpers::fragment_from_fragment_components,
+    json_type::{JsonMapTrait, JsonType, PrimitiveType},
+};
+use std::iter::once;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VisitControl {
+    Continue,
+    // Do not visit the children of the current value (or the value referenced by the current key/index)
+    SkipSubtree,
+    // Terminate the traversal
+    Stop,
+}
+
+// Callbacks invoked by `walk`, all of them receive the JSON Pointer of the current value.
+// Callbacks have a default implementation, so implementors can focus only on the events they are interested in.
+// NOTE: `exit_object` and `exit_array` are invoked even if the subtree has been skipped
+#[allow(unused_variables)]
+pub trait JsonVisitor<'json, T: JsonType> {
+    fn enter_object(&mut self, pointer: &str, value: &'json T) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    // Invoked before visiting the attribute `key`, `pointer` references the attribute value
+    fn key(&mut self, pointer: &str, key: &str) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn exit_object(&mut self, pointer: &str, value: &'json T) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn enter_array(&mut self, pointer: &str, value: &'json T) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    // Invoked before visiting the item at `index`, `pointer` references the item
+    fn index(&mut self, pointer: &str, index: usize) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn exit_array(&mut self, pointer: &str, value: &'json T) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    // Invoked for null, boolean, integer, number and string values
+    fn scalar(&mut self, pointer: &str, value: &'json T) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+fn child_pointer(pointer: &str, fragment_part: &str) -> String {
+    format!("{}{}", pointer, fragment_from_fragment_components(once(fragment_part)))
+}
+
+fn walk_value<'json, T: JsonType, V: JsonVisitor<'json, T>>(value: &'json T, pointer: &str, visitor: &mut V) -> VisitControl {
+    match value.primitive_type() {
+        PrimitiveType::Object => {
+            match visitor.enter_object(pointer, value) {
+                VisitControl::Stop => return VisitControl::Stop,
+                VisitControl::SkipSubtree => {}
+                VisitControl::Continue => {
+                    let keys = value
+                        .as_object()
+                        .map(|object| object.keys().map(ToString::to_string).collect::<Vec<_>>())
+                        .unwrap_or_default();
+                    for key in keys {
+                        let attribute_pointer = child_pointer(pointer, &key);
+                        match visitor.key(&attribute_pointer, &key) {
+                            VisitControl::Stop => return VisitControl::Stop,
+                            VisitControl::SkipSubtree => {}
+                            VisitControl::Continue => {
+                                if let Some(attribute_value) = value.get_attribute(&key) {
+                                    if walk_value(attribute_value, &attribute_pointer, visitor) == VisitControl::Stop {
+                                        return VisitControl::Stop;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            visitor.exit_object(pointer, value)
+        }
+        PrimitiveType::Array => {
+            match visitor.enter_array(pointer, value) {
+                VisitControl::Stop => return VisitControl::Stop,
+                VisitControl::SkipSubtree => {}
+                VisitControl::Continue => {
+                    if let Some(items) = value.as_array() {
+                        for (index, item) in items.enumerate() {
+                            let item_pointer = child_pointer(pointer, &index.to_string());
+                            match visitor.index(&item_pointer, index) {
+                                VisitControl::Stop => return VisitControl::Stop,
+                                VisitControl::SkipSubtree => {}
+                                VisitControl::Continue => {
+                                    if walk_value(item, &item_pointer, visitor) == VisitControl::Stop {
+                                        return VisitControl::Stop;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            visitor.exit_array(pointer, value)
+        }
+        _ => visitor.scalar(pointer, value),
+    }
+}
+
+/// Traverse `value` depth-first invoking the `visitor` callbacks.
+/// `VisitControl::Stop` is returned if the traversal has been terminated by the visitor.
+///
+/// # Panics
+/// The function panics if a traversed value is not recognized as a JSON primitive type (see `JsonType::try_primitive_type`),
+/// the callbacks invoked before reaching such value are not rolled back.
+pub fn walk<'json, T: JsonType, V: JsonVisitor<'json, T>>(value: &'json T, visitor: &mut V) -> VisitControl {
+    if walk_value(value, "", visitor) == VisitControl::Stop {
+        VisitControl::Stop
+    } else {
+        VisitControl::Continue
+    }
+}
+
+// Iterator yielding (JSON Pointer, value) pairs in depth-first (pre-order) order
+#[derive(Debug)]
+pub struct JsonNodes<'json, T: JsonType> {
+    // Nodes still to be yielded, the next one is the last
+    stack: Vec<(String, &'json T)>,
+    // Last yielded node, its children are added to the stack lazily to allow skipping them
+    last_yielded: Option<(String, &'json T)>,
+}
+
+impl<'json, T: JsonType> JsonNodes<'json, T> {
+    // Do not yield the descendants of the last yielded node
+    pub fn skip_subtree(&mut self) {
+        self.last_yielded = None;
+    }
+
+    fn push_children(&mut self, pointer: &str, value: &'json T) {
+        let start = self.stack.len();
+        match value.primitive_type() {
+            PrimitiveType::Object => {
+                if let Some(object) = value.as_object() {
+                    for key in object.keys() {
+                        if let Some(attribute_value) = value.get_attribute(key) {
+                            self.stack.push((child_pointer(pointer, key), attribute_value));
+                        }
+                    }
+                }
+            }
+            PrimitiveType::Array => {
+                if let Some(items) = value.as_array() {
+                    for (index, item) in items.enumerate() {
+                        self.stack.push((child_pointer(pointer, &index.to_string()), item));
+                    }
+                }
+            }
+            _ => {}
+        }
+        // Children are yielded in document order
+        self.stack[start..].reverse();
+    }
+}
+
+impl<'json, T: JsonType> Iterator for JsonNodes<'json, T> {
+    type Item = (String, &'json T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((pointer, value)) = self.last_yielded.take() {
+            self.push_children(&pointer, value);
+        }
+        let (pointer, value) = self.stack.pop()?;
+        self.last_yielded = Some((pointer.clone(), value));
+        Some((pointer, value))
+    }
+}
+
+/// Iterate over all the values contained in `value` (`value` included) in depth-first order
+///
+/// # Panics
+/// The iterator panics while advancing past a value that is not recognized as a JSON primitive type, unless the
+/// descendants of such value are skipped via `JsonNodes::skip_subtree`.
+#[must_use]
+pub fn iter_nodes<T: JsonType>(value: &T) -> JsonNodes<'_, T> {
+    JsonNodes {
+        stack: vec![(String::new(), value)],
+        last_yielded: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{iter_nodes, walk, JsonVisitor, VisitControl};
+    use crate::{json_type::JsonType, rust_type_impl::RustType};
+
+    // Records all the events, `skip` and `stop` define the pointers on which the traversal is controlled
+    #[derive(Default)]
+    struct EventsRecorder {
+        events: Vec<String>,
+        skip: Vec<&'static str>,
+        stop: Vec<&'static str>,
+    }
+
+    impl EventsRecorder {
+        fn record(&mut self, event: String, pointer: &str) -> VisitControl {
+            self.events.push(event);
+            if self.stop.contains(&pointer) {
+                VisitControl::Stop
+            } else if self.skip.contains(&pointer) {
+                VisitControl::SkipSubtree
+            } else {
+                VisitControl::Continue
+            }
+        }
+    }
+
+    impl<'json> JsonVisitor<'json, RustType> for EventsRecorder {
+        fn enter_object(&mut self, pointer: &str, _: &'json RustType) -> VisitControl {
+            self.record(format!("enter_object {pointer}"), pointer)
+        }
+
+        fn key(&mut self, pointer: &str, key: &str) -> VisitControl {
+            self.record(format!("key {pointer} {key}"), pointer)
+        }
+
+        fn exit_object(&mut self, pointer: &str, _: &'json RustType) -> VisitControl {
+            self.events.push(format!("exit_object {pointer}"));
+            VisitControl::Continue
+        }
+
+        fn enter_array(&mut self, pointer: &str, _: &'json RustType) -> VisitControl {
+            self.record(format!("enter_array {pointer}"), pointer)
+        }
+
+        fn index(&mut self, pointer: &str, index: usize) -> VisitControl {
+            self.record(format!("index {pointer} {index}"), pointer)
+        }
+
+        fn exit_array(&mut self, pointer: &str, _: &'json RustType) -> VisitControl {
+            self.events.push(format!("exit_array {pointer}"));
+            VisitControl::Continue
+        }
+
+        fn scalar(&mut self, pointer: &str, value: &'json RustType) -> VisitControl {
+            self.record(format!("scalar {pointer} {value}"), pointer)
+        }
+    }
+
+    fn events(skip: Vec<&'static str>, stop: Vec<&'static str>) -> (VisitControl, Vec<String>) {
+        let value = rust_type!({"a/b": [1, {"c": null}], "d": "e"});
+        let mut recorder = EventsRecorder {
+            skip,
+            stop,
+            ..EventsRecorder::default()
+        };
+        let result = walk(&value, &mut recorder);
+        (result, recorder.events)
+    }
+
+    #[test]
+    fn test_walk() {
+        assert_eq!(
+            events(vec![], vec![]),
+            (
+                VisitControl::Continue,
+                vec![
+                    "enter_object ",
+                    "key /a~1b a/b",
+                    "enter_array /a~1b",
+                    "index /a~1b/0 0",
+                    "scalar /a~1b/0 1",
+                    "index /a~1b/1 1",
+                    "enter_object /a~1b/1",
+                    "key /a~1b/1/c c",
+                    "scalar /a~1b/1/c null",
+                    "exit_object /a~1b/1",
+                    "exit_array /a~1b",
+                    "key /d d",
+                    "scalar /d \"e\"",
+                    "exit_object ",
+                ]
+                .into_iter()
+                .map(ToString::to_string)
+                .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn test_walk_skip_subtree() {
+        assert_eq!(
+            events(vec!["/a~1b/1", "/d"], vec![]).1,
+            vec![
+                "enter_object ",
+                "key /a~1b a/b",
+                "enter_array /a~1b",
+                "index /a~1b/0 0",
+                "scalar /a~1b/0 1",
+                "index /a~1b/1 1",
+                "exit_array /a~1b",
+                "key /d d",
+                "exit_object "
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_stop() {
+        assert_eq!(
+            events(vec![], vec!["/a~1b/0"]),
+            (
+                VisitControl::Stop,
+                vec!["enter_object ", "key /a~1b a/b", "enter_array /a~1b", "index /a~1b/0 0"]
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn test_iter_nodes() {
+        let value = rust_type!({"a": [1, {"b": null}], "c": "d"});
+        let nodes = iter_nodes(&value).map(|(pointer, node)| (pointer, node.clone())).collect::<Vec<_>>();
+        assert_eq!(
+            nodes,
+            vec![
+                (String::new(), value),
+                ("/a".to_string(), rust_type!([1, {"b": null}])),
+                ("/a/0".to_string(), rust_type!(1)),
+                ("/a/1".to_string(), rust_type!({"b": null})),
+                ("/a/1/b".to_string(), rust_type!(null)),
+                ("/c".to_string(), rust_type!("d")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_nodes_skip_subtree() {
+        let value = rust_type!({"a": [1, {"b": null}], "c": "d"});
+        let mut nodes = iter_nodes(&value);
+        let mut pointers = Vec::new();
+        while let Some((pointer, node)) = nodes.next() {
+            if node.is_array() {
+                nodes.skip_subtree();
+            }
+            pointers.push(pointer);
+        }
+        assert_eq!(pointers, vec!["", "/a", "/c"]);
+    }
+
+    #[cfg(feature = "trait_serde_json")]
+    #[test]
+    fn test_iter_nodes_on_serde_json() {
+        let value = json!({"a": {"b": [true, "c"]}});
+        let strings = iter_nodes(&value).filter(|(_, node)| node.is_string()).map(|(pointer, _)| pointer).collect::<Vec<_>>();
+        assert_eq!(strings, vec!["/a/b/1"]);
+    }
+}